@@ -0,0 +1,65 @@
+use crate::season::Season;
+use chrono::NaiveDate;
+
+pub const NHL_API: &str = "https://statsapi.web.nhl.com";
+
+/// Where the web app sends API requests. Set `GAMES_TODAY_API` when
+/// building to point it at a proxy, e.g. `GAMES_TODAY_API=http://localhost:8090`.
+pub fn base_url() -> &'static str {
+    option_env!("GAMES_TODAY_API").unwrap_or(NHL_API)
+}
+
+pub fn url(path: &str) -> String {
+    format!(
+        "{}/{}",
+        base_url().trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+pub fn schedule_path(date: NaiveDate) -> String {
+    format!(
+        "api/v1/schedule?expand=schedule.linescore&date={}",
+        date.format("%F")
+    )
+}
+
+pub fn schedule_range_path(start: NaiveDate, end: NaiveDate, team_id: Option<usize>) -> String {
+    let mut path = format!(
+        "api/v1/schedule?expand=schedule.linescore&startDate={}&endDate={}",
+        start.format("%F"),
+        end.format("%F")
+    );
+    if let Some(team_id) = team_id {
+        path.push_str(&format!("&teamId={}", team_id));
+    }
+    path
+}
+
+/// Regular season and playoff games for a whole season.
+pub fn season_schedule_path(season: Season, team_id: Option<usize>) -> String {
+    let mut path = format!(
+        "api/v1/schedule?expand=schedule.linescore&season={}&gameType=R,P",
+        season.id()
+    );
+    if let Some(team_id) = team_id {
+        path.push_str(&format!("&teamId={}", team_id));
+    }
+    path
+}
+
+pub fn game_schedule_path(game_pk: usize) -> String {
+    format!(
+        "api/v1/schedule?expand=schedule.linescore&gamePk={}",
+        game_pk
+    )
+}
+
+pub fn live_feed_path(game_pk: usize) -> String {
+    format!("api/v1/game/{}/feed/live", game_pk)
+}
+
+/// Historical player records, used for the Gordle word lists.
+pub const RECORDS_API: &str = "https://records.nhl.com";
+
+pub const PLAYER_RECORDS_PATH: &str = "site/api/player?include=id&include=firstName&include=lastName";
@@ -0,0 +1,133 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use anyhow::Error;
+    use chrono::{Duration, Local, NaiveDate};
+    use games_today::{
+        api,
+        normalized::NormalizedGame,
+        teams::{self, TeamInfo},
+        upstream::{get_json, FixtureUpstream, HttpUpstream, Upstream},
+        NextGameSchedule,
+    };
+    use serde::Deserialize;
+    use std::{path::PathBuf, sync::Arc};
+    use structopt::StructOpt;
+    use tide::{Body, Request, Response, StatusCode};
+
+    #[derive(Debug, StructOpt)]
+    #[structopt(name = "api_server", about = "Serve normalized NHL games as JSON")]
+    struct Opt {
+        /// Address to listen on
+        #[structopt(long, default_value = "127.0.0.1:8080")]
+        listen: String,
+        /// Serve recorded fixtures instead of calling the NHL API
+        #[structopt(long, parse(from_os_str))]
+        fixtures: Option<PathBuf>,
+        /// Save every upstream response to this directory
+        #[structopt(long, parse(from_os_str))]
+        record: Option<PathBuf>,
+    }
+
+    #[derive(Clone)]
+    struct State {
+        upstream: Arc<dyn Upstream>,
+    }
+
+    #[derive(Deserialize)]
+    struct DateQuery {
+        date: Option<NaiveDate>,
+    }
+
+    #[derive(Deserialize)]
+    struct RangeQuery {
+        start: Option<NaiveDate>,
+        end: Option<NaiveDate>,
+    }
+
+    fn json_response<T: serde::Serialize>(value: &T) -> tide::Result {
+        Ok(Response::builder(StatusCode::Ok)
+            .body(Body::from_json(value)?)
+            .build())
+    }
+
+    fn not_found(message: String) -> tide::Result {
+        Ok(Response::builder(StatusCode::NotFound)
+            .body(Body::from_json(&serde_json::json!({ "error": message }))?)
+            .build())
+    }
+
+    async fn fetch_games(state: &State, path: &str) -> tide::Result<Vec<NormalizedGame>> {
+        let schedule: NextGameSchedule = get_json(state.upstream.as_ref(), path)
+            .await
+            .map_err(|err| tide::Error::new(StatusCode::BadGateway, err))?;
+        Ok(schedule
+            .dates
+            .iter()
+            .flat_map(|date| date.games.iter())
+            .map(NormalizedGame::from)
+            .collect())
+    }
+
+    async fn games(req: Request<State>) -> tide::Result {
+        let query: DateQuery = req.query()?;
+        let date = query.date.unwrap_or_else(|| Local::now().date_naive());
+        json_response(&fetch_games(req.state(), &api::schedule_path(date)).await?)
+    }
+
+    async fn all_teams(_req: Request<State>) -> tide::Result {
+        let mut teams: Vec<&TeamInfo> = teams::TEAM_REGISTRY.values().collect();
+        teams.sort_by_key(|team| team.id);
+        json_response(&teams)
+    }
+
+    async fn team_schedule(req: Request<State>) -> tide::Result {
+        let abbreviation = req.param("abbr")?;
+        let team = match teams::team_by_abbreviation(abbreviation) {
+            Some(team) => team,
+            None => return not_found(format!("unknown team {}", abbreviation)),
+        };
+        let query: RangeQuery = req.query()?;
+        let start = query.start.unwrap_or_else(|| Local::now().date_naive());
+        let end = query.end.unwrap_or(start + Duration::days(30));
+        let path = api::schedule_range_path(start, end, Some(team.id));
+        json_response(&fetch_games(req.state(), &path).await?)
+    }
+
+    async fn game(req: Request<State>) -> tide::Result {
+        let game_pk: usize = req
+            .param("pk")?
+            .parse()
+            .map_err(|err| tide::Error::new(StatusCode::BadRequest, err))?;
+        let games = fetch_games(req.state(), &api::game_schedule_path(game_pk)).await?;
+        match games.into_iter().find(|game| game.game_pk == game_pk) {
+            Some(game) => json_response(&game),
+            None => not_found(format!("unknown game {}", game_pk)),
+        }
+    }
+
+    pub async fn run() -> Result<(), Error> {
+        tide::log::start();
+        let opt = Opt::from_args();
+        let upstream: Arc<dyn Upstream> = match (opt.fixtures, opt.record) {
+            (Some(dir), _) => Arc::new(FixtureUpstream::new(dir)),
+            (None, Some(dir)) => Arc::new(HttpUpstream::default().recording(dir)),
+            (None, None) => Arc::new(HttpUpstream::default()),
+        };
+        let mut app = tide::with_state(State { upstream });
+        app.at("/games").get(games);
+        app.at("/teams").get(all_teams);
+        app.at("/team/:abbr/schedule").get(team_schedule);
+        app.at("/game/:pk").get(game);
+        app.listen(opt.listen).await?;
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_std::main]
+async fn main() -> Result<(), anyhow::Error> {
+    server::run().await
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,544 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod cli {
+    use anyhow::{anyhow, bail, Error};
+    use futures_util::{stream, StreamExt};
+    use chrono::{Duration, Local, NaiveDate, Utc};
+    use chrono_english::{parse_date_string, Dialect};
+    use games_today::{
+        api,
+        boxscore::BoxScore,
+        cache::FileStore,
+        changes::Snapshot,
+        drift::Model,
+        franchise,
+        gordle,
+        head_to_head::HeadToHead,
+        ics::{self, GameFilter},
+        league::{self, League},
+        live::LiveFeed,
+        ratings::{trend_arrow, Ratings},
+        locale::Locale,
+        season::Season,
+        teams, travel,
+        upstream::{self, get_json, FixtureUpstream, HttpUpstream, Upstream},
+        win_probability,
+        Content, Game, NextGameSchedule, PlayerRecordData,
+    };
+    use std::{collections::HashMap, path::PathBuf};
+    use structopt::StructOpt;
+
+    #[derive(Debug, StructOpt)]
+    #[structopt(name = "games", about = "NHL games from the command line")]
+    enum Command {
+        /// Export games as an iCalendar file
+        Ics {
+            /// First date to include, e.g. "today" or "2022-11-20"
+            #[structopt(long, default_value = "today")]
+            start: String,
+            /// Last date to include, defaults to 30 days after the start
+            #[structopt(long)]
+            end: Option<String>,
+            /// Only include games for this team, by abbreviation (e.g. SJS)
+            #[structopt(long = "team")]
+            teams: Vec<String>,
+            /// Only include games for the favorite teams
+            #[structopt(long)]
+            favorites: bool,
+            /// Write to a file instead of stdout
+            #[structopt(short, long, parse(from_os_str))]
+            output: Option<PathBuf>,
+        },
+        /// Print the box score for a finished game
+        Boxscore {
+            /// The game id, e.g. 2022020275
+            game_pk: usize,
+        },
+        /// Show the results between two teams, season by season
+        HeadToHead {
+            /// A team abbreviation, e.g. SJS
+            team: String,
+            /// The other team's abbreviation, e.g. EDM
+            other_team: String,
+            /// How many seasons back to look, counting this one
+            #[structopt(long, default_value = "5")]
+            seasons: i32,
+        },
+        /// Show rest days, road trips and travel for a team's season
+        Travel {
+            /// A team abbreviation, e.g. SJS
+            team: String,
+            /// The year the season starts, defaults to the current season
+            #[structopt(long)]
+            season: Option<i32>,
+        },
+        /// Show how the schedule changed since the last run, saving a new
+        /// snapshot to compare against next time
+        Changes {
+            /// First date to watch, e.g. "today" or "2022-11-20"
+            #[structopt(long, default_value = "today")]
+            start: String,
+            /// How many days to watch
+            #[structopt(long, default_value = "14")]
+            days: i64,
+            /// Where snapshots are kept, defaults to the cache directory
+            #[structopt(long, parse(from_os_str))]
+            snapshots: Option<PathBuf>,
+        },
+        /// Show Elo power ratings from this season's results and the last
+        Ratings {
+            /// How many seasons to replay, counting this one
+            #[structopt(long, default_value = "2")]
+            seasons: i32,
+            /// Show this team's rating game by game, e.g. SJS
+            #[structopt(long)]
+            team: Option<String>,
+        },
+        /// Check how well the pregame win probabilities matched a season's
+        /// results
+        Calibration {
+            /// The year the season starts, defaults to last season
+            #[structopt(long)]
+            season: Option<i32>,
+            /// Load the season from recorded fixtures instead of the NHL API
+            #[structopt(long, parse(from_os_str))]
+            fixtures: Option<PathBuf>,
+            /// Save the season's schedule to this directory
+            #[structopt(long, parse(from_os_str))]
+            record: Option<PathBuf>,
+        },
+        /// Show one day's games across several leagues, in start order
+        Leagues {
+            /// A league to include, e.g. ahl; defaults to all of them
+            #[structopt(long = "league")]
+            leagues: Vec<League>,
+            /// The date to show, e.g. "today" or "2022-11-20"
+            #[structopt(long, default_value = "today")]
+            date: String,
+            /// Save every league's response to this directory
+            #[structopt(long, parse(from_os_str))]
+            record: Option<PathBuf>,
+        },
+        /// Check API responses against our models, exiting non-zero on drift
+        SchemaCheck {
+            /// Saved responses to check
+            #[structopt(parse(from_os_str))]
+            files: Vec<PathBuf>,
+            /// Check every recorded response in this directory
+            #[structopt(long, parse(from_os_str))]
+            fixtures: Option<PathBuf>,
+            /// Fetch this API path and check it, e.g. "api/v1/schedule?date=2022-11-20"
+            #[structopt(long)]
+            fetch: Option<String>,
+            /// schedule, content, live-feed or player-records; guessed from
+            /// the file name when not given
+            #[structopt(long)]
+            model: Option<Model>,
+            /// Also fail on unknown and defaulted fields
+            #[structopt(long)]
+            strict: bool,
+            /// List unknown and defaulted fields rather than counting them
+            #[structopt(short, long)]
+            verbose: bool,
+        },
+        /// Build a Gordle word list from the NHL player records
+        GordleWords {
+            /// Read a saved records response instead of fetching it
+            #[structopt(long, parse(from_os_str))]
+            records: Option<PathBuf>,
+            /// Save the fetched records response to this file
+            #[structopt(long, parse(from_os_str))]
+            save_records: Option<PathBuf>,
+            /// Letters per name
+            #[structopt(long, default_value = "5")]
+            length: usize,
+            /// Print a Rust array instead of one name per line
+            #[structopt(long)]
+            rust: bool,
+        },
+    }
+
+    fn parse_date(text: &str) -> Result<NaiveDate, Error> {
+        Ok(parse_date_string(text, Local::now(), Dialect::Us)?.date_naive())
+    }
+
+    fn team_id(abbreviation: &str) -> Result<usize, Error> {
+        teams::team_by_abbreviation(abbreviation)
+            .map(|team| team.id)
+            .ok_or_else(|| anyhow!("unknown team {}", abbreviation))
+    }
+
+    /// Prints the findings for one response and returns whether it counts
+    /// as drift.
+    fn report_drift(
+        name: &str,
+        model: Model,
+        text: &str,
+        strict: bool,
+        verbose: bool,
+    ) -> Result<bool, Error> {
+        let findings = model.check(text)?;
+        let drifted = findings
+            .iter()
+            .any(|finding| strict || finding.kind.is_breaking());
+        println!(
+            "{} ({}): {}",
+            name,
+            model.name(),
+            if drifted { "DRIFT" } else { "ok" }
+        );
+        let (listed, counted): (Vec<_>, Vec<_>) = findings
+            .iter()
+            .partition(|finding| verbose || strict || finding.kind.is_breaking());
+        for finding in listed {
+            println!("  {}", finding);
+        }
+        if !counted.is_empty() {
+            println!(
+                "  {} unknown or defaulted fields, --verbose to list them",
+                counted.len()
+            );
+        }
+        Ok(drifted)
+    }
+
+    fn word_list_name(length: usize) -> String {
+        let number = match length {
+            4 => "FOUR".to_string(),
+            5 => "FIVE".to_string(),
+            6 => "SIX".to_string(),
+            7 => "SEVEN".to_string(),
+            length => length.to_string(),
+        };
+        format!("{}_LETTER_LAST_NAMES", number)
+    }
+
+    /// How many game content requests the calendar export has in flight.
+    const CONTENT_FETCHES: usize = 8;
+
+    async fn export_calendar(
+        start: NaiveDate,
+        end: NaiveDate,
+        filter: GameFilter,
+    ) -> Result<String, Error> {
+        let team_id = match &filter {
+            GameFilter::Teams(team_ids) if team_ids.len() == 1 => Some(team_ids[0]),
+            _ => None,
+        };
+        let upstream = HttpUpstream::default();
+        let schedule: NextGameSchedule =
+            get_json(&upstream, &api::schedule_range_path(start, end, team_id)).await?;
+        let games: Vec<Game> = schedule
+            .dates
+            .into_iter()
+            .flat_map(|date| date.games)
+            .filter(|game| filter.matches(game))
+            .collect();
+
+        let contents: Vec<Result<Content, Error>> = stream::iter(&games)
+            .map(|game| get_json(&upstream, &game.content.link))
+            .buffered(CONTENT_FETCHES)
+            .collect()
+            .await;
+        let mut previews = HashMap::new();
+        for (game, content) in games.iter().zip(contents) {
+            match content {
+                Ok(content) => {
+                    previews.insert(game.game_pk, content.preview_string().unwrap_or_default());
+                }
+                Err(err) => log::info!("no preview for {}: {}", game.game_pk, err),
+            }
+        }
+        Ok(ics::calendar(&games, &previews, &filter, Utc::now()))
+    }
+
+    pub async fn run() -> Result<(), Error> {
+        match Command::from_args() {
+            Command::Ics {
+                start,
+                end,
+                teams: team_abbreviations,
+                favorites,
+                output,
+            } => {
+                let start = parse_date(&start)?;
+                let end = match end {
+                    Some(end) => parse_date(&end)?,
+                    None => start + Duration::days(30),
+                };
+                if end < start {
+                    bail!("the end date {} is before the start date {}", end, start);
+                }
+                let filter = if favorites {
+                    GameFilter::favorites()
+                } else if team_abbreviations.is_empty() {
+                    GameFilter::All
+                } else {
+                    let team_ids = team_abbreviations
+                        .iter()
+                        .map(|abbreviation| team_id(abbreviation))
+                        .collect::<Result<Vec<_>, Error>>()?;
+                    GameFilter::Teams(team_ids)
+                };
+                let calendar = export_calendar(start, end, filter).await?;
+                match output {
+                    Some(path) => std::fs::write(path, calendar)?,
+                    None => print!("{}", calendar),
+                }
+            }
+            Command::Boxscore { game_pk } => {
+                let upstream = HttpUpstream::default();
+                let feed: LiveFeed = get_json(&upstream, &api::live_feed_path(game_pk)).await?;
+                let box_score = BoxScore::from_feed(&feed)
+                    .ok_or_else(|| anyhow!("no box score for {}", game_pk))?;
+                print!("{}", box_score.to_text());
+            }
+            Command::HeadToHead {
+                team,
+                other_team,
+                seasons,
+            } => {
+                let (team_id, other_team_id) = (team_id(&team)?, team_id(&other_team)?);
+                let current = Season::for_date(Local::now().date_naive());
+                let upstream = HttpUpstream::default();
+                let mut games = vec![];
+                for start_year in (current.start_year - seasons + 1)..=current.start_year {
+                    let season = Season { start_year };
+                    if season.cancelled() {
+                        continue;
+                    }
+                    let schedule: NextGameSchedule =
+                        get_json(&upstream, &api::season_schedule_path(season, Some(team_id)))
+                            .await?;
+                    games.extend(schedule.dates.into_iter().flat_map(|date| date.games));
+                }
+                print!("{}", HeadToHead::new(&games, team_id, other_team_id).to_text());
+            }
+            Command::Travel { team, season } => {
+                let team_id = team_id(&team)?;
+                let season = match season {
+                    Some(start_year) => Season { start_year },
+                    None => Season::for_date(Local::now().date_naive()),
+                };
+                let upstream = HttpUpstream::default();
+                let schedule: NextGameSchedule =
+                    get_json(&upstream, &api::season_schedule_path(season, Some(team_id))).await?;
+                let games: Vec<Game> = schedule
+                    .dates
+                    .into_iter()
+                    .flat_map(|date| date.games)
+                    .collect();
+                print!(
+                    "{}",
+                    travel::schedule_text(&travel::team_schedule(&games, team_id))
+                );
+            }
+            Command::Changes {
+                start,
+                days,
+                snapshots,
+            } => {
+                if days < 1 {
+                    bail!("the number of days must be at least 1, not {}", days);
+                }
+                let start = parse_date(&start)?;
+                let end = start + Duration::days(days - 1);
+                let store = FileStore::new(
+                    snapshots.unwrap_or_else(|| FileStore::default_dir().join("snapshots")),
+                );
+                let upstream = HttpUpstream::default();
+                let schedule: NextGameSchedule =
+                    get_json(&upstream, &api::schedule_range_path(start, end, None)).await?;
+                let mut snapshot = Snapshot::new(schedule, start, end, Utc::now());
+                match Snapshot::load(&store) {
+                    Some(previous) => {
+                        // Look up games that left the window, so ones moved
+                        // to another date show as rescheduled, not removed.
+                        for game_pk in previous.missing_from(&snapshot) {
+                            let moved: NextGameSchedule =
+                                get_json(&upstream, &api::game_schedule_path(game_pk)).await?;
+                            snapshot.dates.extend(moved.dates);
+                        }
+                        let changes = previous.changes_to(&snapshot);
+                        let taken = previous.taken.with_timezone(&Local);
+                        if changes.is_empty() {
+                            println!("No changes since {}", taken.format("%F %R"));
+                        } else {
+                            println!("Changes since {}:", taken.format("%F %R"));
+                        }
+                        let offset = -(Local::now().offset().local_minus_utc() as f64);
+                        for change in changes {
+                            println!("  {}", change.describe(offset));
+                        }
+                    }
+                    None => println!("Saved the first snapshot, changes will show next time"),
+                }
+                snapshot.save(&store);
+            }
+            Command::Ratings { seasons, team } => {
+                let current = Season::for_date(Local::now().date_naive());
+                let upstream = HttpUpstream::default();
+                let mut games = vec![];
+                for start_year in (current.start_year - seasons + 1)..=current.start_year {
+                    let season = Season { start_year };
+                    if season.cancelled() {
+                        continue;
+                    }
+                    let schedule: NextGameSchedule =
+                        get_json(&upstream, &api::season_schedule_path(season, None)).await?;
+                    games.extend(schedule.dates.into_iter().flat_map(|date| date.games));
+                }
+                let ratings = Ratings::from_games(&games);
+                match team {
+                    Some(team) => {
+                        for point in ratings.history(team_id(&team)?) {
+                            println!(
+                                "{} {:<3} {:>5.0} {:>+5.1} {}",
+                                point.date.format("%F"),
+                                franchise::abbreviation(point.opponent_id).unwrap_or("?"),
+                                point.rating,
+                                point.change,
+                                trend_arrow(point.change)
+                            );
+                        }
+                    }
+                    None => print!("{}", ratings.to_text()),
+                }
+            }
+            Command::Calibration {
+                season,
+                fixtures,
+                record,
+            } => {
+                let season = match season {
+                    Some(start_year) => Season { start_year },
+                    None => Season {
+                        start_year: Season::for_date(Local::now().date_naive()).start_year - 1,
+                    },
+                };
+                let upstream: Box<dyn Upstream> = match (fixtures, record) {
+                    (Some(dir), _) => Box::new(FixtureUpstream::new(dir)),
+                    (None, Some(dir)) => Box::new(HttpUpstream::default().recording(dir)),
+                    (None, None) => Box::new(HttpUpstream::default()),
+                };
+                let schedule: NextGameSchedule =
+                    get_json(upstream.as_ref(), &api::season_schedule_path(season, None)).await?;
+                let games: Vec<Game> = schedule
+                    .dates
+                    .into_iter()
+                    .flat_map(|date| date.games)
+                    .collect();
+                println!("{}", season.label());
+                print!(
+                    "{}",
+                    win_probability::calibration_text(&win_probability::backtest(&games))
+                );
+            }
+            Command::Leagues {
+                leagues,
+                date,
+                record,
+            } => {
+                let date = parse_date(&date)?;
+                let today = Local::now().date_naive();
+                let leagues = if leagues.is_empty() {
+                    League::ALL.to_vec()
+                } else {
+                    leagues
+                };
+                let mut games = vec![];
+                for league in leagues {
+                    let adapter = league.adapter();
+                    let mut upstream = HttpUpstream::new(adapter.base_url());
+                    if let Some(dir) = &record {
+                        upstream = upstream.recording(dir);
+                    }
+                    let text = upstream.get(&adapter.schedule_path(date, today)).await?;
+                    games.extend(adapter.parse_schedule(&text, date)?);
+                }
+                let offset = -(Local::now().offset().local_minus_utc() as f64);
+                for game in league::merged_timeline(games) {
+                    println!("{}", game.describe(offset, Locale::English));
+                }
+            }
+            Command::SchemaCheck {
+                mut files,
+                fixtures,
+                fetch,
+                model,
+                strict,
+                verbose,
+            } => {
+                if let Some(dir) = fixtures {
+                    for entry in std::fs::read_dir(dir)? {
+                        files.push(entry?.path());
+                    }
+                    files.sort();
+                }
+                let mut drifted = 0;
+                for file in files {
+                    let name = file.display().to_string();
+                    let guessed = file
+                        .file_name()
+                        .and_then(|name| Model::for_fixture(&name.to_string_lossy()));
+                    match model.or(guessed) {
+                        Some(model) => {
+                            let text = std::fs::read_to_string(&file)?;
+                            if report_drift(&name, model, &text, strict, verbose)? {
+                                drifted += 1;
+                            }
+                        }
+                        None => println!("{}: skipped, no model for this response", name),
+                    }
+                }
+                if let Some(path) = fetch {
+                    let model = model
+                        .or_else(|| Model::for_fixture(&upstream::fixture_name(&path)))
+                        .ok_or_else(|| anyhow!("no model for {}, pass --model", path))?;
+                    let text = HttpUpstream::default().get(&path).await?;
+                    if report_drift(&path, model, &text, strict, verbose)? {
+                        drifted += 1;
+                    }
+                }
+                if drifted > 0 {
+                    bail!("{} responses have drifted from the models", drifted);
+                }
+            }
+            Command::GordleWords {
+                records,
+                save_records,
+                length,
+                rust,
+            } => {
+                let text = match records {
+                    Some(path) => std::fs::read_to_string(path)?,
+                    None => {
+                        let upstream = HttpUpstream::new(api::RECORDS_API);
+                        upstream.get(api::PLAYER_RECORDS_PATH).await?
+                    }
+                };
+                if let Some(path) = save_records {
+                    std::fs::write(path, &text)?;
+                }
+                let records: PlayerRecordData = serde_json::from_str(&text)?;
+                let words = gordle::word_list(&records, length);
+                if rust {
+                    print!("{}", gordle::word_list_source(&word_list_name(length), &words));
+                } else {
+                    for word in words {
+                        println!("{}", word);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_std::main]
+async fn main() -> Result<(), anyhow::Error> {
+    cli::run().await
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -1,6 +1,6 @@
-use games_today::pages::GamesToday;
+use games_today::pages::App;
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
-    yew::start_app::<GamesToday>();
+    yew::start_app::<App>();
 }
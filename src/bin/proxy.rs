@@ -0,0 +1,118 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod proxy {
+    use anyhow::Error;
+    use games_today::{
+        cache::{CacheStore, FileStore, MemoryStore},
+        proxy::Proxy,
+        upstream::{HttpUpstream, Upstream},
+    };
+    use std::{
+        path::{Component, Path, PathBuf},
+        sync::Arc,
+    };
+    use structopt::StructOpt;
+    use tide::{
+        http::{headers::HeaderValue, mime},
+        security::{CorsMiddleware, Origin},
+        Body, Request, Response, StatusCode,
+    };
+
+    #[derive(Debug, StructOpt)]
+    #[structopt(name = "proxy", about = "Caching, CORS friendly proxy for the NHL API")]
+    struct Opt {
+        /// Address to listen on
+        #[structopt(long, default_value = "127.0.0.1:8090")]
+        listen: String,
+        /// API to forward requests to
+        #[structopt(long, default_value = games_today::api::NHL_API)]
+        upstream: String,
+        /// Keep cached responses on disk instead of in memory
+        #[structopt(long, parse(from_os_str))]
+        cache_dir: Option<PathBuf>,
+        /// Also serve the built web app from this directory, e.g. dist
+        #[structopt(long = "static", parse(from_os_str))]
+        static_dir: Option<PathBuf>,
+    }
+
+    type Store = Box<dyn CacheStore + Send + Sync>;
+
+    #[derive(Clone)]
+    struct State {
+        proxy: Arc<Proxy<Store>>,
+        static_dir: Option<PathBuf>,
+    }
+
+    async fn forward(req: Request<State>) -> tide::Result {
+        let mut path = req.url().path().to_string();
+        if let Some(query) = req.url().query() {
+            path.push('?');
+            path.push_str(query);
+        }
+        match req.state().proxy.get(&path).await {
+            Ok(text) => Ok(Response::builder(StatusCode::Ok)
+                .content_type(mime::JSON)
+                .body(text)
+                .build()),
+            Err(err) => Err(tide::Error::new(StatusCode::BadGateway, err)),
+        }
+    }
+
+    /// Serves files from the static directory, falling back to index.html
+    /// so routes handled by the web app still load.
+    async fn serve_static(req: Request<State>) -> tide::Result {
+        let dir = match &req.state().static_dir {
+            Some(dir) => dir,
+            None => return Ok(Response::new(StatusCode::NotFound)),
+        };
+        let relative = Path::new(req.url().path().trim_start_matches('/'));
+        let safe = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        let file = dir.join(relative);
+        let file = if safe && file.is_file() {
+            file
+        } else {
+            dir.join("index.html")
+        };
+        Ok(Response::builder(StatusCode::Ok)
+            .body(Body::from_file(file).await?)
+            .build())
+    }
+
+    pub async fn run() -> Result<(), Error> {
+        tide::log::start();
+        let opt = Opt::from_args();
+        let upstream: Arc<dyn Upstream> = Arc::new(HttpUpstream::new(opt.upstream));
+        let store: Store = match opt.cache_dir {
+            Some(dir) => Box::new(FileStore::new(dir)),
+            None => Box::new(MemoryStore::default()),
+        };
+        let mut app = tide::with_state(State {
+            proxy: Arc::new(Proxy::new(upstream, store)),
+            static_dir: opt.static_dir,
+        });
+        app.with(
+            CorsMiddleware::new()
+                .allow_methods(
+                    "GET, OPTIONS"
+                        .parse::<HeaderValue>()
+                        .map_err(|err| err.into_inner())?,
+                )
+                .allow_origin(Origin::from("*")),
+        );
+        app.at("/api/*").get(forward);
+        app.at("/").get(serve_static);
+        app.at("/*").get(serve_static);
+        app.listen(opt.listen).await?;
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_std::main]
+async fn main() -> Result<(), anyhow::Error> {
+    proxy::run().await
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
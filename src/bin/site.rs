@@ -0,0 +1,137 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod site {
+    use anyhow::{anyhow, Error};
+    use chrono::{Local, NaiveDate};
+    use chrono_english::{parse_date_string, Dialect};
+    use chrono_tz::Tz;
+    use games_today::{
+        api,
+        locale::Locale,
+        site::{self, SiteDay},
+        summary::{TeamNames, TextFormatter, TimeFormat},
+        teams::{self, TeamInfo},
+        upstream::{get_json, FixtureUpstream, HttpUpstream, Upstream},
+        Content, NextGameSchedule,
+    };
+    use std::{collections::HashMap, path::PathBuf};
+    use structopt::StructOpt;
+
+    const STYLES: &str = include_str!("../../styles.css");
+
+    #[derive(Debug, StructOpt)]
+    #[structopt(name = "site", about = "Pre-render plain HTML pages of NHL games")]
+    struct Opt {
+        /// First date to render, e.g. "yesterday" or "2022-11-20"
+        #[structopt(long, default_value = "today")]
+        start: String,
+        /// Last date to render, defaults to the start date
+        #[structopt(long)]
+        end: Option<String>,
+        /// Load responses from recorded fixtures instead of the NHL API
+        #[structopt(long, parse(from_os_str))]
+        fixtures: Option<PathBuf>,
+        /// Save every API response to this directory
+        #[structopt(long, parse(from_os_str))]
+        record: Option<PathBuf>,
+        /// Output directory
+        #[structopt(short, long, parse(from_os_str), default_value = "site")]
+        out: PathBuf,
+        /// Time zone for start times, e.g. America/Los_Angeles
+        #[structopt(long, default_value = "America/New_York")]
+        time_zone: Tz,
+        /// Show start times on a 24-hour clock
+        #[structopt(long = "24-hour")]
+        twenty_four_hour: bool,
+        /// How to name teams: full, abbreviation or nickname
+        #[structopt(long, default_value = "full")]
+        names: TeamNames,
+        /// Language for labels, dates and times: en or fr
+        #[structopt(long, default_value = "en")]
+        locale: Locale,
+        /// Public URL of the site, used for links in the feed
+        #[structopt(long, default_value = "")]
+        base_url: String,
+    }
+
+    fn parse_date(text: &str) -> Result<NaiveDate, Error> {
+        Ok(parse_date_string(text, Local::now(), Dialect::Us)?.date_naive())
+    }
+
+    pub async fn run() -> Result<(), Error> {
+        let opt = Opt::from_args();
+        let start = parse_date(&opt.start)?;
+        let end = match &opt.end {
+            Some(end) => parse_date(end)?,
+            None => start,
+        };
+        if end < start {
+            return Err(anyhow!("end date {} is before start date {}", end, start));
+        }
+        let upstream: Box<dyn Upstream> = match (&opt.fixtures, &opt.record) {
+            (Some(dir), _) => Box::new(FixtureUpstream::new(dir)),
+            (None, Some(dir)) => Box::new(HttpUpstream::default().recording(dir)),
+            (None, None) => Box::new(HttpUpstream::default()),
+        };
+
+        let mut days = vec![];
+        let mut previews = HashMap::new();
+        let mut date = start;
+        while date <= end {
+            let schedule: NextGameSchedule =
+                get_json(upstream.as_ref(), &api::schedule_path(date)).await?;
+            let games: Vec<_> = schedule
+                .dates
+                .into_iter()
+                .flat_map(|date| date.games)
+                .collect();
+            for game in games
+                .iter()
+                .filter(|game| game.is_live() || game.is_preview())
+            {
+                match get_json::<Content>(upstream.as_ref(), &game.content.link).await {
+                    Ok(content) => {
+                        previews.insert(game.game_pk, content.preview_string().unwrap_or_default());
+                    }
+                    Err(err) => log::info!("no preview for {}: {}", game.game_pk, err),
+                }
+            }
+            days.push(SiteDay { date, games });
+            date = date.succ_opt().expect("date");
+        }
+
+        let mut teams: Vec<&TeamInfo> = teams::TEAM_REGISTRY.values().collect();
+        teams.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut formatter = TextFormatter::default()
+            .localized(opt.locale)
+            .team_names(opt.names);
+        if opt.twenty_four_hour {
+            formatter = formatter.time_format(TimeFormat::TwentyFourHour);
+        }
+        for page in site::build(
+            &days,
+            &teams,
+            &previews,
+            opt.time_zone,
+            &formatter,
+            &opt.base_url,
+        ) {
+            let path = opt.out.join(&page.path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, page.contents)?;
+        }
+        std::fs::write(opt.out.join("styles.css"), STYLES)?;
+        println!("wrote {} days to {}", days.len(), opt.out.display());
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_std::main]
+async fn main() -> Result<(), anyhow::Error> {
+    site::run().await
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,216 @@
+use crate::{
+    live::{LiveFeed, Person},
+    BoxscoreTeam, TeamSkaterStats,
+};
+
+/// One line of the box score, away value first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatRow {
+    pub label: String,
+    pub away: String,
+    pub home: String,
+}
+
+impl StatRow {
+    fn new(label: &str, away: impl ToString, home: impl ToString) -> Self {
+        Self {
+            label: label.to_string(),
+            away: away.to_string(),
+            home: home.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoalieLine {
+    pub team: String,
+    pub name: String,
+    pub saves: usize,
+    pub shots: usize,
+    pub decision: String,
+}
+
+impl GoalieLine {
+    pub fn describe(&self) -> String {
+        let decision = if self.decision.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", self.decision)
+        };
+        format!(
+            "{} {}: {} saves on {} shots{}",
+            self.team, self.name, self.saves, self.shots, decision
+        )
+    }
+}
+
+/// Everything shown for a finished game, laid out once so the web page
+/// and the command line print the same thing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoxScore {
+    pub away: String,
+    pub home: String,
+    pub periods: Vec<StatRow>,
+    pub stats: Vec<StatRow>,
+    pub goalies: Vec<GoalieLine>,
+    pub stars: Vec<String>,
+}
+
+fn power_play(stats: &TeamSkaterStats) -> String {
+    format!(
+        "{}/{}",
+        stats.power_play_goals as usize, stats.power_play_opportunities as usize
+    )
+}
+
+fn team_stat_rows(away: &TeamSkaterStats, home: &TeamSkaterStats) -> Vec<StatRow> {
+    vec![
+        StatRow::new("Shots", away.shots, home.shots),
+        StatRow::new("Power play", power_play(away), power_play(home)),
+        StatRow::new(
+            "Faceoff %",
+            &away.face_off_win_percentage,
+            &home.face_off_win_percentage,
+        ),
+        StatRow::new("Hits", away.hits, home.hits),
+        StatRow::new("Blocks", away.blocked, home.blocked),
+        StatRow::new("PIM", away.pim, home.pim),
+        StatRow::new("Giveaways", away.giveaways, home.giveaways),
+        StatRow::new("Takeaways", away.takeaways, home.takeaways),
+    ]
+}
+
+fn goalie_lines(team: &BoxscoreTeam) -> Vec<GoalieLine> {
+    team.goalies_played()
+        .into_iter()
+        .map(|(player, stats)| GoalieLine {
+            team: team.team.abbreviation.clone(),
+            name: player.person.full_name.clone(),
+            saves: stats.saves,
+            shots: stats.shots,
+            decision: stats.decision.clone(),
+        })
+        .collect()
+}
+
+impl BoxScore {
+    /// `None` until the feed carries a boxscore.
+    pub fn from_feed(feed: &LiveFeed) -> Option<Self> {
+        let boxscore = feed.live_data.boxscore.as_ref()?;
+        let teams = &boxscore.teams;
+        let linescore = &feed.live_data.linescore;
+
+        let mut periods: Vec<StatRow> = linescore
+            .periods
+            .iter()
+            .map(|period| StatRow::new(&period.ordinal_num, period.away.goals, period.home.goals))
+            .collect();
+        let away_stats = &teams.away.team_stats.team_skater_stats;
+        let home_stats = &teams.home.team_stats.team_skater_stats;
+        periods.push(StatRow::new("T", away_stats.goals, home_stats.goals));
+
+        let decisions = &feed.live_data.decisions;
+        let star = |person: &Person| {
+            let team = if teams.home.player(person.id).is_some() {
+                &teams.home
+            } else {
+                &teams.away
+            };
+            format!("{} ({})", person.full_name, team.team.abbreviation)
+        };
+        let stars = [
+            &decisions.first_star,
+            &decisions.second_star,
+            &decisions.third_star,
+        ]
+        .iter()
+        .filter_map(|person| person.as_ref().map(star))
+        .collect();
+
+        let mut goalies = goalie_lines(&teams.away);
+        goalies.extend(goalie_lines(&teams.home));
+
+        Some(Self {
+            away: teams.away.team.abbreviation.clone(),
+            home: teams.home.team.abbreviation.clone(),
+            periods,
+            stats: team_stat_rows(away_stats, home_stats),
+            goalies,
+            stars,
+        })
+    }
+
+    /// Plain text with aligned columns, for the terminal.
+    pub fn to_text(&self) -> String {
+        let width = self
+            .stats
+            .iter()
+            .chain(self.periods.iter())
+            .map(|row| row.label.len())
+            .max()
+            .unwrap_or_default();
+        let row = |label: &str, away: &str, home: &str| {
+            format!("{:<width$} {:>6} {:>6}\n", label, away, home, width = width)
+        };
+        let mut text = row("", &self.away, &self.home);
+        for line in self.periods.iter().chain(self.stats.iter()) {
+            text.push_str(&row(&line.label, &line.away, &line.home));
+        }
+        if !self.goalies.is_empty() {
+            text.push_str("\nGoalies\n");
+            for goalie in &self.goalies {
+                text.push_str(&format!("  {}\n", goalie.describe()));
+            }
+        }
+        if !self.stars.is_empty() {
+            text.push_str("\nThree stars\n");
+            for (index, star) in self.stars.iter().enumerate() {
+                text.push_str(&format!("  {}. {}\n", index + 1, star));
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn box_score() -> BoxScore {
+        let feed: LiveFeed = serde_json::from_str(include_str!(
+            "../data/fixtures/game-2022020275-feed-live.json"
+        ))
+        .unwrap();
+        BoxScore::from_feed(&feed).unwrap()
+    }
+
+    #[test]
+    fn test_box_score() {
+        let box_score = box_score();
+        assert_eq!(box_score.periods.len(), 5);
+        assert_eq!(box_score.periods[3], StatRow::new("OT", 0, 1));
+        assert_eq!(box_score.periods[4], StatRow::new("T", 3, 4));
+        assert_eq!(box_score.stats[1], StatRow::new("Power play", "1/1", "1/1"));
+        assert_eq!(
+            box_score.goalies[1].describe(),
+            "SJS James Reimer: 29 saves on 32 shots (W)"
+        );
+        assert_eq!(
+            box_score.stars,
+            [
+                "Erik Karlsson (SJS)",
+                "Tomas Hertl (SJS)",
+                "Connor McDavid (EDM)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let text = box_score().to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "              EDM    SJS");
+        assert_eq!(lines[8], "Faceoff %    46.3   53.7");
+        assert!(text.contains("\nThree stars\n  1. Erik Karlsson (SJS)\n"));
+    }
+}
@@ -0,0 +1,474 @@
+use crate::{
+    season::Season, ContentLink, Game, GameState, Linescore, NextGameSchedule, Status, Team,
+    TeamAtGame, Teams,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Mutex};
+
+pub const KEY_PREFIX: &str = "games_today";
+
+/// How long a game's data can be reused before it has to be fetched again.
+/// `None` means the data will never change.
+pub fn game_ttl(game: &Game) -> Option<Duration> {
+    state_ttl(game.state())
+}
+
+pub fn state_ttl(state: GameState) -> Option<Duration> {
+    match state {
+        GameState::Final => None,
+        GameState::Live => Some(Duration::seconds(15)),
+        GameState::Preview => Some(Duration::minutes(10)),
+        GameState::Postponed => Some(Duration::hours(1)),
+    }
+}
+
+/// A schedule is only as fresh as its most volatile game.
+pub fn schedule_ttl(schedule: &NextGameSchedule) -> Option<Duration> {
+    let mut games = schedule
+        .dates
+        .iter()
+        .flat_map(|date| date.games.iter())
+        .peekable();
+    if games.peek().is_none() {
+        return Some(Duration::hours(6));
+    }
+    games
+        .map(game_ttl)
+        .fold(None, |shortest, ttl| match (shortest, ttl) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+}
+
+/// A whole season only changes as its remaining games are played, so it
+/// is kept for an hour until none are left.
+pub fn season_ttl(schedule: &NextGameSchedule) -> Option<Duration> {
+    let unsettled = schedule
+        .dates
+        .iter()
+        .flat_map(|date| date.games.iter())
+        .any(|game| game.is_live() || game.is_preview());
+    if unsettled {
+        Some(Duration::hours(1))
+    } else {
+        None
+    }
+}
+
+/// A season's game cut down to what the ratings, rest and season series
+/// read, so two seasons of them fit in localStorage.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SeasonGame {
+    game_pk: usize,
+    start: DateTime<Utc>,
+    game_type: String,
+    state: GameState,
+    /// Team id and score.
+    away: (usize, usize),
+    home: (usize, usize),
+    /// The last period played, which tells overtime games apart.
+    period: usize,
+}
+
+impl From<&Game> for SeasonGame {
+    fn from(game: &Game) -> Self {
+        let team = |team: &TeamAtGame| (team.team.id, team.score);
+        Self {
+            game_pk: game.game_pk,
+            start: game.game_date,
+            game_type: game.game_type.clone(),
+            state: game.state(),
+            away: team(&game.teams.away),
+            home: team(&game.teams.home),
+            period: game.linescore.current_period,
+        }
+    }
+}
+
+impl SeasonGame {
+    fn into_game(self, season: Season) -> Game {
+        let (abstract_game_state, detailed_state) = match self.state {
+            GameState::Final => ("Final", "Final"),
+            GameState::Live => ("Live", "In Progress"),
+            GameState::Preview => ("Preview", "Scheduled"),
+            GameState::Postponed => ("Preview", "Postponed"),
+        };
+        let team = |(id, score)| TeamAtGame {
+            score,
+            team: Team {
+                id,
+                name: String::new(),
+            },
+        };
+        Game {
+            game_pk: self.game_pk,
+            game_date: self.start,
+            game_type: self.game_type,
+            season: season.id(),
+            content: ContentLink {
+                link: String::new(),
+            },
+            teams: Teams {
+                home: team(self.home),
+                away: team(self.away),
+            },
+            status: Status {
+                detailed_state: detailed_state.to_string(),
+                abstract_game_state: abstract_game_state.to_string(),
+            },
+            linescore: Linescore {
+                current_period: self.period,
+                ..Linescore::default()
+            },
+        }
+    }
+}
+
+pub trait CacheStore {
+    fn load(&self, key: &str) -> Option<String>;
+    fn store(&self, key: &str, value: &str);
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry<T> {
+    stored_at: DateTime<Utc>,
+    ttl_seconds: Option<i64>,
+    value: T,
+}
+
+impl<T> CacheEntry<T> {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        match self.ttl_seconds {
+            Some(ttl) => now < self.stored_at + Duration::seconds(ttl),
+            None => true,
+        }
+    }
+}
+
+/// A cached value along with whether it is still within its TTL. Stale
+/// values are still handed out so the page can render while offline.
+#[derive(Debug, Clone)]
+pub struct Cached<T> {
+    pub value: T,
+    pub fresh: bool,
+}
+
+pub struct Cache<S> {
+    store: S,
+}
+
+impl<S: CacheStore> Cache<S> {
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    pub fn load<T: for<'de> Deserialize<'de>>(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Option<Cached<T>> {
+        let text = self.store.load(key)?;
+        let entry: CacheEntry<T> = serde_json::from_str(&text).ok()?;
+        Some(Cached {
+            fresh: entry.is_fresh(now),
+            value: entry.value,
+        })
+    }
+
+    pub fn save<T: Serialize>(
+        &self,
+        key: &str,
+        value: T,
+        ttl: Option<Duration>,
+        now: DateTime<Utc>,
+    ) {
+        let entry = CacheEntry {
+            stored_at: now,
+            ttl_seconds: ttl.map(|ttl| ttl.num_seconds()),
+            value,
+        };
+        if let Ok(text) = serde_json::to_string(&entry) {
+            self.store.store(key, &text);
+        }
+    }
+
+    pub fn schedule(
+        &self,
+        date: NaiveDate,
+        now: DateTime<Utc>,
+    ) -> Option<Cached<NextGameSchedule>> {
+        self.load(&schedule_key(date), now)
+    }
+
+    pub fn put_schedule(&self, date: NaiveDate, schedule: &NextGameSchedule, now: DateTime<Utc>) {
+        self.save(&schedule_key(date), schedule, schedule_ttl(schedule), now);
+    }
+
+    /// A season's games, with only what `SeasonGame` keeps.
+    pub fn season(&self, season: Season, now: DateTime<Utc>) -> Option<Cached<Vec<Game>>> {
+        let cached: Cached<Vec<SeasonGame>> = self.load(&season_key(season), now)?;
+        Some(Cached {
+            value: cached
+                .value
+                .into_iter()
+                .map(|game| game.into_game(season))
+                .collect(),
+            fresh: cached.fresh,
+        })
+    }
+
+    pub fn put_season(&self, season: Season, schedule: &NextGameSchedule, now: DateTime<Utc>) {
+        let games: Vec<SeasonGame> = schedule
+            .dates
+            .iter()
+            .flat_map(|date| date.games.iter())
+            .map(SeasonGame::from)
+            .collect();
+        self.save(&season_key(season), games, season_ttl(schedule), now);
+    }
+
+    pub fn preview(&self, game_pk: usize, now: DateTime<Utc>) -> Option<Cached<String>> {
+        self.load(&preview_key(game_pk), now)
+    }
+
+    pub fn put_preview(&self, game: &Game, preview: &str, now: DateTime<Utc>) {
+        self.save(&preview_key(game.game_pk), preview, game_ttl(game), now);
+    }
+}
+
+fn schedule_key(date: NaiveDate) -> String {
+    format!("{}:schedule:{}", KEY_PREFIX, date.format("%F"))
+}
+
+fn season_key(season: Season) -> String {
+    format!("{}:season:{}", KEY_PREFIX, season.id())
+}
+
+fn preview_key(game_pk: usize) -> String {
+    format!("{}:preview:{}", KEY_PREFIX, game_pk)
+}
+
+impl<S: CacheStore + ?Sized> CacheStore for Box<S> {
+    fn load(&self, key: &str) -> Option<String> {
+        (**self).load(key)
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        (**self).store(key, value)
+    }
+}
+
+/// Cache that only lives as long as the process.
+#[derive(Default)]
+pub struct MemoryStore(Mutex<HashMap<String, String>>);
+
+impl CacheStore for MemoryStore {
+    fn load(&self, key: &str) -> Option<String> {
+        self.0.lock().ok()?.get(key).cloned()
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        if let Ok(mut entries) = self.0.lock() {
+            entries.insert(key.to_string(), value.to_string());
+        }
+    }
+}
+
+/// Browser cache backed by `window.localStorage`.
+#[cfg(feature = "web_app")]
+pub struct LocalStorageStore;
+
+#[cfg(feature = "web_app")]
+impl CacheStore for LocalStorageStore {
+    fn load(&self, key: &str) -> Option<String> {
+        let storage = web_sys::window()?.local_storage().ok()??;
+        storage.get_item(key).ok()?
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let Some(storage) = storage {
+            if storage.set_item(key, value).is_err() {
+                log::info!("unable to cache {}", key);
+            }
+        }
+    }
+}
+
+/// Native cache that keeps one JSON file per key.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStore {
+    dir: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStore {
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$XDG_CACHE_HOME/games_today`, falling back to `~/.cache/games_today`.
+    pub fn default_dir() -> std::path::PathBuf {
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(std::path::PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".cache"))
+            })
+            .unwrap_or_else(std::env::temp_dir);
+        base.join(KEY_PREFIX)
+    }
+
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", name))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CacheStore for FileStore {
+    fn load(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)).ok()
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        let result =
+            std::fs::create_dir_all(&self.dir).and_then(|_| std::fs::write(self.path(key), value));
+        if let Err(err) = result {
+            log::info!("unable to cache {}: {}", key, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn schedule() -> NextGameSchedule {
+        serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json")).unwrap()
+    }
+
+    fn game(schedule: &NextGameSchedule, state: GameState) -> Game {
+        schedule.dates[0]
+            .games
+            .iter()
+            .find(|game| game.state() == state)
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_ttl_by_state() {
+        let schedule = schedule();
+        assert_eq!(game_ttl(&game(&schedule, GameState::Final)), None);
+        assert_eq!(
+            game_ttl(&game(&schedule, GameState::Live)),
+            Some(Duration::seconds(15))
+        );
+        assert_eq!(schedule_ttl(&schedule), Some(Duration::seconds(15)));
+        assert_eq!(
+            schedule_ttl(&NextGameSchedule::default()),
+            Some(Duration::hours(6))
+        );
+    }
+
+    #[test]
+    fn test_finished_schedule_never_expires() {
+        let mut schedule = schedule();
+        schedule.dates[0].games.retain(|game| game.is_finished());
+        let cache = Cache::new(MemoryStore::default());
+        let date = NaiveDate::from_ymd_opt(2022, 11, 20).unwrap();
+        let stored_at = Utc::now();
+        cache.put_schedule(date, &schedule, stored_at);
+
+        let cached = cache
+            .schedule(date, stored_at + Duration::days(365))
+            .unwrap();
+        assert!(cached.fresh);
+        assert_eq!(cached.value.dates[0].games.len(), 2);
+    }
+
+    #[test]
+    fn test_season_expires_until_played_out() {
+        let season = Season { start_year: 2022 };
+        let cache = Cache::new(MemoryStore::default());
+        let stored_at = Utc::now();
+        let mut schedule = schedule();
+        cache.put_season(season, &schedule, stored_at);
+        assert!(cache.season(season, stored_at).unwrap().fresh);
+        assert!(
+            !cache
+                .season(season, stored_at + Duration::hours(2))
+                .unwrap()
+                .fresh
+        );
+
+        schedule.dates[0].games.retain(|game| game.is_finished());
+        cache.put_season(season, &schedule, stored_at);
+        let cached = cache
+            .season(season, stored_at + Duration::days(365))
+            .unwrap();
+        assert!(cached.fresh);
+        assert!(cache
+            .season(Season { start_year: 2021 }, stored_at)
+            .is_none());
+    }
+
+    #[test]
+    fn test_season_keeps_what_ratings_read() {
+        let season = Season { start_year: 2022 };
+        let cache = Cache::new(MemoryStore::default());
+        let schedule = schedule();
+        cache.put_season(season, &schedule, Utc::now());
+        let raw = serde_json::to_string(&schedule).unwrap();
+        assert!(cache.store.load(&season_key(season)).unwrap().len() < raw.len() / 2);
+
+        let games = cache.season(season, Utc::now()).unwrap().value;
+        assert_eq!(games.len(), schedule.dates[0].games.len());
+        for (cached, game) in games.iter().zip(schedule.dates[0].games.iter()) {
+            assert_eq!(cached.game_pk, game.game_pk);
+            assert_eq!(cached.game_date, game.game_date);
+            assert_eq!(cached.state(), game.state());
+            assert_eq!(cached.went_to_overtime(), game.went_to_overtime());
+            assert_eq!(cached.teams.home.score, game.teams.home.score);
+            assert_eq!(
+                cached.teams.away.team.display_name(),
+                game.teams.away.team.display_name()
+            );
+            assert_eq!(cached.season, "20222023");
+        }
+    }
+
+    #[test]
+    fn test_live_preview_goes_stale() {
+        let schedule = schedule();
+        let live = game(&schedule, GameState::Live);
+        let cache = Cache::new(MemoryStore::default());
+        let stored_at = Utc::now();
+        cache.put_preview(&live, "TNT", stored_at);
+
+        assert!(
+            cache
+                .preview(live.game_pk, stored_at + Duration::seconds(5))
+                .unwrap()
+                .fresh
+        );
+        let stale = cache
+            .preview(live.game_pk, stored_at + Duration::minutes(1))
+            .unwrap();
+        assert!(!stale.fresh);
+        assert_eq!(stale.value, "TNT");
+        assert!(cache.preview(1, stored_at).is_none());
+    }
+}
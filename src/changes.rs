@@ -0,0 +1,348 @@
+use crate::{cache::CacheStore, locale::Locale, Game, GameDate, NextGameSchedule};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The schedule for a range of dates as it looked at one moment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub taken: DateTime<Utc>,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub dates: Vec<GameDate>,
+}
+
+impl Snapshot {
+    pub const KEY: &'static str = "schedule-snapshot";
+
+    pub fn new(
+        schedule: NextGameSchedule,
+        start: NaiveDate,
+        end: NaiveDate,
+        taken: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            taken,
+            start,
+            end,
+            dates: schedule.dates,
+        }
+    }
+
+    pub fn load(store: &impl CacheStore) -> Option<Self> {
+        serde_json::from_str(&store.load(Self::KEY)?).ok()
+    }
+
+    pub fn save(&self, store: &impl CacheStore) {
+        if let Ok(text) = serde_json::to_string(self) {
+            store.store(Self::KEY, &text);
+        }
+    }
+
+    fn games_between(&self, start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = &Game> {
+        self.dates
+            .iter()
+            .filter(move |date| {
+                date.date
+                    .parse::<NaiveDate>()
+                    .is_ok_and(|date| start <= date && date <= end)
+            })
+            .flat_map(|date| date.games.iter())
+    }
+
+    /// Games over the dates both snapshots cover that `later` doesn't have.
+    /// They were dropped or moved to another date; adding their new dates
+    /// to `later` lets `changes_to` tell which.
+    pub fn missing_from(&self, later: &Snapshot) -> Vec<usize> {
+        let (start, end) = (self.start.max(later.start), self.end.min(later.end));
+        let later_games: Vec<usize> = later
+            .games_between(start, end)
+            .map(|game| game.game_pk)
+            .collect();
+        self.games_between(start, end)
+            .map(|game| game.game_pk)
+            .filter(|game_pk| !later_games.contains(game_pk))
+            .collect()
+    }
+
+    /// What changed between this snapshot and a later one, over the dates
+    /// both cover. A game moved out of those dates shows up as
+    /// rescheduled when `later` also has its new date, and as removed
+    /// otherwise.
+    pub fn changes_to(&self, later: &Snapshot) -> Vec<ScheduleChange> {
+        let (start, end) = (self.start.max(later.start), self.end.min(later.end));
+        if start > end {
+            return vec![];
+        }
+        let later_games: HashMap<usize, &Game> = later
+            .dates
+            .iter()
+            .flat_map(|date| date.games.iter())
+            .map(|game| (game.game_pk, game))
+            .collect();
+        let mut seen = vec![];
+        let mut changes = vec![];
+        for before in self.games_between(start, end) {
+            seen.push(before.game_pk);
+            match later_games.get(&before.game_pk) {
+                Some(after) => changes.extend(game_changes(before, after)),
+                None => changes.push(ScheduleChange::new(before, ChangeKind::Removed)),
+            }
+        }
+        changes.extend(
+            later
+                .games_between(start, end)
+                .filter(|game| !seen.contains(&game.game_pk))
+                .map(|game| ScheduleChange::new(game, ChangeKind::Added)),
+        );
+        changes.sort_by_key(|change| (change.start, change.game_pk));
+        changes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// The start moved, possibly to another day.
+    Rescheduled {
+        from: DateTime<Utc>,
+    },
+    Postponed,
+    /// A game whose time was TBD now has one.
+    TimeSet,
+    StatusChanged {
+        from: String,
+        to: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleChange {
+    pub game_pk: usize,
+    pub away: String,
+    pub home: String,
+    /// The start after the change, or before it for a removed game.
+    pub start: DateTime<Utc>,
+    pub kind: ChangeKind,
+}
+
+impl ScheduleChange {
+    fn new(game: &Game, kind: ChangeKind) -> Self {
+        Self {
+            game_pk: game.game_pk,
+            away: game.teams.away.team.display_name().to_string(),
+            home: game.teams.home.team.display_name().to_string(),
+            start: game.game_date,
+            kind,
+        }
+    }
+
+    /// "Rescheduled: Chicago Blackhawks @ Winnipeg Jets from Sun Nov 20
+    /// 4:00 PM to Tue Jan 10 5:00 PM"
+    pub fn describe(&self, offset: f64) -> String {
+        let tz = FixedOffset::west_opt(offset as i32).unwrap();
+        let when = |start: &DateTime<Utc>| {
+            let start = start.with_timezone(&tz);
+            format!(
+                "{} {}",
+                start.format("%a %b %-d"),
+                Locale::English
+                    .time(&start, false)
+                    .trim_start_matches('\u{a0}')
+            )
+        };
+        let matchup = format!("{} @ {}", self.away, self.home);
+        match &self.kind {
+            ChangeKind::Added => format!("Added: {}, {}", matchup, when(&self.start)),
+            ChangeKind::Removed => format!("Removed: {}, {}", matchup, when(&self.start)),
+            ChangeKind::Rescheduled { from } => format!(
+                "Rescheduled: {} from {} to {}",
+                matchup,
+                when(from),
+                when(&self.start)
+            ),
+            ChangeKind::Postponed => format!(
+                "Postponed: {}, {}",
+                matchup,
+                self.start.with_timezone(&tz).format("%a %b %-d")
+            ),
+            ChangeKind::TimeSet => format!("Time set: {}, {}", matchup, when(&self.start)),
+            ChangeKind::StatusChanged { from, to } => {
+                format!("{}: {} -> {}", matchup, from, to)
+            }
+        }
+    }
+}
+
+/// States a game passes through as it's played, which aren't news for the
+/// schedule.
+const PLAY_STATES: &[&str] = &[
+    "Scheduled",
+    "Pre-Game",
+    "In Progress",
+    "In Progress - Critical",
+    "Game Over",
+    "Final",
+];
+
+fn game_changes(before: &Game, after: &Game) -> Vec<ScheduleChange> {
+    let mut changes = vec![];
+    if before.is_tbd() && !after.is_tbd() {
+        if !after.is_postponed() {
+            changes.push(ScheduleChange::new(after, ChangeKind::TimeSet));
+        }
+    } else if before.game_date != after.game_date {
+        changes.push(ScheduleChange::new(
+            after,
+            ChangeKind::Rescheduled {
+                from: before.game_date,
+            },
+        ));
+    }
+    if !before.is_postponed() && after.is_postponed() {
+        changes.push(ScheduleChange::new(after, ChangeKind::Postponed));
+    } else if before.detailed_state() != after.detailed_state()
+        && !before.is_tbd()
+        && !(PLAY_STATES.contains(&before.detailed_state())
+            && PLAY_STATES.contains(&after.detailed_state()))
+    {
+        changes.push(ScheduleChange::new(
+            after,
+            ChangeKind::StatusChanged {
+                from: before.detailed_state().to_string(),
+                to: after.detailed_state().to_string(),
+            },
+        ));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::Value;
+
+    fn snapshot(schedule: &Value, taken: &str) -> Snapshot {
+        let date = NaiveDate::from_ymd_opt(2022, 11, 20).unwrap();
+        Snapshot::new(
+            serde_json::from_value(schedule.clone()).unwrap(),
+            date,
+            date,
+            taken.parse().unwrap(),
+        )
+    }
+
+    fn schedule() -> Value {
+        serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json")).unwrap()
+    }
+
+    #[test]
+    fn test_changes() {
+        let mut schedule = schedule();
+        let games = schedule["dates"][0]["games"].as_array_mut().unwrap();
+        let added = games.pop().unwrap();
+        games[4]["status"]["detailedState"] = Value::from("Scheduled (Time TBD)");
+        let before = snapshot(&schedule, "2022-11-19T12:00:00Z");
+        assert!(before.changes_to(&before).is_empty());
+
+        let games = schedule["dates"][0]["games"].as_array_mut().unwrap();
+        games.push(added);
+        games.remove(1);
+        // The TBD game gets its time, and the game that was in progress is
+        // postponed.
+        games[3]["status"]["detailedState"] = Value::from("Scheduled");
+        games[3]["gameDate"] = Value::from("2022-11-21T03:30:00Z");
+        games[1]["status"]["detailedState"] = Value::from("Postponed");
+        let after = snapshot(&schedule, "2022-11-20T12:00:00Z");
+
+        let offset = 8.0 * 3600.0;
+        let changes: Vec<String> = before
+            .changes_to(&after)
+            .iter()
+            .map(|change| change.describe(offset))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "Added: Chicago Blackhawks @ Winnipeg Jets, Sun Nov 20 4:00 PM",
+                "Removed: Boston Bruins @ Montréal Canadiens, Sun Nov 20 4:30 PM",
+                "Postponed: Toronto Maple Leafs @ Dallas Stars, Sun Nov 20",
+                "Time set: Detroit Red Wings @ Vegas Golden Knights, Sun Nov 20 7:30 PM",
+            ]
+        );
+
+        // Looking the removed game up finds it on a later date.
+        let mut after = after;
+        assert_eq!(before.missing_from(&after), [2022020276]);
+        let mut moved = self::schedule();
+        let game = moved["dates"][0]["games"][1].clone();
+        moved["dates"][0]["date"] = Value::from("2023-01-10");
+        moved["dates"][0]["games"] = Value::from(vec![game]);
+        moved["dates"][0]["games"][0]["gameDate"] = Value::from("2023-01-11T00:00:00Z");
+        let moved: NextGameSchedule = serde_json::from_value(moved).unwrap();
+        after.dates.extend(moved.dates);
+        assert_eq!(
+            before.changes_to(&after).last().unwrap().describe(offset),
+            "Rescheduled: Boston Bruins @ Montréal Canadiens from Sun Nov 20 4:30 PM to Tue Jan 10 4:00 PM"
+        );
+    }
+
+    #[test]
+    fn test_postponed_tbd_game_and_play_states() {
+        let mut schedule = schedule();
+        let games = schedule["dates"][0]["games"].as_array_mut().unwrap();
+        games[4]["status"]["detailedState"] = Value::from("Scheduled (Time TBD)");
+        let before = snapshot(&schedule, "2022-11-19T12:00:00Z");
+
+        let games = schedule["dates"][0]["games"].as_array_mut().unwrap();
+        games[4]["status"]["detailedState"] = Value::from("Postponed");
+        // Games being played and finished aren't schedule changes.
+        games[2]["status"]["detailedState"] = Value::from("Final");
+        games[3]["status"]["detailedState"] = Value::from("In Progress - Critical");
+        let after = snapshot(&schedule, "2022-11-20T12:00:00Z");
+        let kinds: Vec<ChangeKind> = before
+            .changes_to(&after)
+            .into_iter()
+            .map(|change| change.kind)
+            .collect();
+        assert_eq!(kinds, [ChangeKind::Postponed]);
+
+        let games = schedule["dates"][0]["games"].as_array_mut().unwrap();
+        games[4]["status"]["detailedState"] = Value::from("Scheduled");
+        let later = snapshot(&schedule, "2022-11-21T12:00:00Z");
+        let changes = after.changes_to(&later);
+        assert_eq!(
+            changes[0].kind,
+            ChangeKind::StatusChanged {
+                from: "Postponed".to_string(),
+                to: "Scheduled".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_games_moved_out_of_range_are_rescheduled() {
+        let mut schedule = schedule();
+        let before = snapshot(&schedule, "2022-11-19T12:00:00Z");
+        let game = schedule["dates"][0]["games"]
+            .as_array_mut()
+            .unwrap()
+            .remove(0);
+        let mut moved = game.clone();
+        moved["gameDate"] = Value::from("2023-01-11T01:00:00Z");
+        schedule["dates"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({ "date": "2023-01-10", "games": [moved] }));
+        let mut after = snapshot(&schedule, "2022-11-21T12:00:00Z");
+        after.end = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+        let changes = before.changes_to(&after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes[0].describe(8.0 * 3600.0),
+            "Rescheduled: Edmonton Oilers @ San Jose Sharks from Sun Nov 20 4:00 PM to Tue Jan 10 5:00 PM"
+        );
+    }
+}
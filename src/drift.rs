@@ -0,0 +1,309 @@
+use crate::{live::LiveFeed, Content, NextGameSchedule, PlayerRecordData};
+use anyhow::{bail, Error};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// The responses we have models for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    Schedule,
+    Content,
+    LiveFeed,
+    PlayerRecords,
+}
+
+impl Model {
+    pub const ALL: [Model; 4] = [
+        Model::Schedule,
+        Model::Content,
+        Model::LiveFeed,
+        Model::PlayerRecords,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Model::Schedule => "schedule",
+            Model::Content => "content",
+            Model::LiveFeed => "live-feed",
+            Model::PlayerRecords => "player-records",
+        }
+    }
+
+    /// Guesses the model from a fixture name, as `upstream::fixture_name`
+    /// writes them.
+    pub fn for_fixture(name: &str) -> Option<Self> {
+        let name = name.trim_end_matches(".json");
+        if name.starts_with("schedule") {
+            Some(Model::Schedule)
+        } else if name.ends_with("-content") {
+            Some(Model::Content)
+        } else if name.ends_with("-feed-live") {
+            Some(Model::LiveFeed)
+        } else if name.starts_with("player") {
+            Some(Model::PlayerRecords)
+        } else {
+            None
+        }
+    }
+
+    pub fn check(&self, text: &str) -> Result<Vec<Finding>, Error> {
+        match self {
+            Model::Schedule => check::<NextGameSchedule>(text),
+            Model::Content => check::<Content>(text),
+            Model::LiveFeed => check::<LiveFeed>(text),
+            Model::PlayerRecords => check::<PlayerRecordData>(text),
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Error> {
+        match Model::ALL.iter().find(|model| model.name() == text) {
+            Some(model) => Ok(*model),
+            None => bail!(
+                "unknown model '{}', expected schedule, content, live-feed or player-records",
+                text
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DriftKind {
+    /// A field the model requires isn't there.
+    MissingField,
+    /// A value isn't the type the model expects.
+    WrongType,
+    /// Any other reason the response doesn't deserialize.
+    Invalid,
+    /// The model has the field, but the response doesn't, so it was
+    /// defaulted.
+    Defaulted,
+    /// The response has a field the model ignores.
+    UnknownField,
+}
+
+impl DriftKind {
+    /// Whether the response can't be read at all.
+    pub fn is_breaking(&self) -> bool {
+        matches!(
+            self,
+            DriftKind::MissingField | DriftKind::WrongType | DriftKind::Invalid
+        )
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            DriftKind::MissingField => "missing",
+            DriftKind::WrongType => "wrong type",
+            DriftKind::Invalid => "invalid",
+            DriftKind::Defaulted => "defaulted",
+            DriftKind::UnknownField => "unknown",
+        }
+    }
+}
+
+/// One kind of drift at one place in the response. Array indices and
+/// keys that are ids, like the boxscore's `ID8475717`, are dropped from
+/// the path, so a field missing from every game is reported once with a
+/// count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub kind: DriftKind,
+    /// e.g. `dates[].games[].linescore.currentPeriod` or
+    /// `liveData.boxscore.teams.away.players.*.position`
+    pub path: String,
+    pub count: usize,
+    pub message: Option<String>,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<10} {}", self.kind.label(), self.path)?;
+        if self.count > 1 {
+            write!(f, " (x{})", self.count)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares a response against `T`. Anything that stops it deserializing
+/// is reported on its own, since serde stops at the first problem;
+/// otherwise the model is serialized back and its fields compared with
+/// the response's.
+pub fn check<T: DeserializeOwned + Serialize>(text: &str) -> Result<Vec<Finding>, Error> {
+    let original: Value = serde_json::from_str(text)?;
+    let model: T = match serde_path_to_error::deserialize(&original) {
+        Ok(model) => model,
+        Err(err) => return Ok(vec![deserialize_finding(&err)]),
+    };
+    let round_trip = serde_json::to_value(&model)?;
+    let mut counts = BTreeMap::new();
+    compare(&original, &round_trip, "", &mut counts);
+    Ok(counts
+        .into_iter()
+        .map(|((kind, path), count)| Finding {
+            kind,
+            path,
+            count,
+            message: None,
+        })
+        .collect())
+}
+
+fn deserialize_finding(err: &serde_path_to_error::Error<serde_json::Error>) -> Finding {
+    let message = err.inner().to_string();
+    let kind = if message.starts_with("missing field") {
+        DriftKind::MissingField
+    } else if message.starts_with("invalid type") {
+        DriftKind::WrongType
+    } else {
+        DriftKind::Invalid
+    };
+    Finding {
+        kind,
+        path: collapse_indices(&err.path().to_string()),
+        count: 1,
+        message: Some(message),
+    }
+}
+
+/// `dates[0].games[3]` becomes `dates[].games[]`, and
+/// `players.ID8475717` becomes `players.*`.
+fn collapse_indices(path: &str) -> String {
+    let mut collapsed = String::new();
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                in_index = true;
+                collapsed.push_str("[]");
+            }
+            ']' => in_index = false,
+            _ if in_index => (),
+            c => collapsed.push(c),
+        }
+    }
+    collapsed
+        .split('.')
+        .map(collapse_id)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Map keys that are ids, like `ID8475717` or `28`, become `*`.
+fn collapse_id(key: &str) -> &str {
+    let digits = key.trim_start_matches("ID");
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        "*"
+    } else {
+        key
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    let key = collapse_id(key);
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn compare(
+    original: &Value,
+    round_trip: &Value,
+    path: &str,
+    counts: &mut BTreeMap<(DriftKind, String), usize>,
+) {
+    match (original, round_trip) {
+        (Value::Object(original), Value::Object(round_trip)) => {
+            for (key, value) in original {
+                let field = join(path, key);
+                match round_trip.get(key) {
+                    Some(round_trip) => compare(value, round_trip, &field, counts),
+                    None => *counts.entry((DriftKind::UnknownField, field)).or_default() += 1,
+                }
+            }
+            for key in round_trip.keys() {
+                if !original.contains_key(key) {
+                    *counts
+                        .entry((DriftKind::Defaulted, join(path, key)))
+                        .or_default() += 1;
+                }
+            }
+        }
+        (Value::Array(original), Value::Array(round_trip)) => {
+            let element = format!("{}[]", path);
+            for (original, round_trip) in original.iter().zip(round_trip) {
+                compare(original, round_trip, &element, counts);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_match_models() {
+        let findings = Model::Schedule
+            .check(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+            .unwrap();
+        assert!(findings.iter().all(|finding| !finding.kind.is_breaking()));
+        let findings = Model::LiveFeed
+            .check(include_str!(
+                "../data/fixtures/game-2022020275-feed-live.json"
+            ))
+            .unwrap();
+        assert!(findings.iter().all(|finding| !finding.kind.is_breaking()));
+    }
+
+    #[test]
+    fn test_drift() {
+        let text = include_str!("../data/fixtures/schedule-2022-11-20.json");
+        let mut schedule: Value = serde_json::from_str(text).unwrap();
+        let games = schedule["dates"][0]["games"].as_array_mut().unwrap();
+        for game in games.iter_mut() {
+            game["venueTimeZone"] = Value::from("America/Los_Angeles");
+        }
+        games[1]["linescore"]
+            .as_object_mut()
+            .unwrap()
+            .remove("currentPeriodOrdinal");
+        let findings = Model::Schedule.check(&schedule.to_string()).unwrap();
+        let unknown = findings
+            .iter()
+            .find(|finding| finding.path == "dates[].games[].venueTimeZone")
+            .unwrap();
+        assert_eq!(unknown.kind, DriftKind::UnknownField);
+        assert_eq!(unknown.count, 7);
+        assert!(findings
+            .iter()
+            .any(|finding| finding.kind == DriftKind::Defaulted
+                && finding.path == "dates[].games[].linescore.currentPeriodOrdinal"));
+
+        schedule["dates"][0]["games"][2]["gamePk"] = Value::from("2022020276");
+        let findings = Model::Schedule.check(&schedule.to_string()).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, DriftKind::WrongType);
+        assert_eq!(findings[0].path, "dates[].games[].gamePk");
+
+        schedule["dates"][0]["games"][2]["gamePk"] = Value::from(2022020276);
+        schedule["dates"][0]["games"][2]
+            .as_object_mut()
+            .unwrap()
+            .remove("teams");
+        let findings = Model::Schedule.check(&schedule.to_string()).unwrap();
+        assert_eq!(findings[0].kind, DriftKind::MissingField);
+        assert!(findings[0].to_string().contains("missing field `teams`"));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{teams, Franchise, FranchiseData, Game};
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+
+const FRANCHISES_TEXT: &str = include_str!("../data/franchises.json");
+
+/// Every franchise, keyed by its id in the records API.
+pub static FRANCHISES: Lazy<HashMap<usize, Franchise>> = Lazy::new(|| {
+    let franchises: FranchiseData = serde_json::from_str(FRANCHISES_TEXT).expect("from_str");
+    franchises
+        .data
+        .into_iter()
+        .map(|franchise| (franchise.id, franchise))
+        .collect()
+});
+
+/// A team id that `data/teams.json` doesn't list: a team that moved,
+/// was renamed or folded, or one newer than the checked-in data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineageTeam {
+    pub id: usize,
+    pub name: &'static str,
+    pub abbreviation: &'static str,
+    pub franchise_id: usize,
+    /// Seasons are written like the API does, e.g. 19791980.
+    pub first_season: u32,
+    pub last_season: Option<u32>,
+}
+
+const fn team(
+    id: usize,
+    name: &'static str,
+    abbreviation: &'static str,
+    franchise_id: usize,
+    first_season: u32,
+    last_season: Option<u32>,
+) -> LineageTeam {
+    LineageTeam {
+        id,
+        name,
+        abbreviation,
+        franchise_id,
+        first_season,
+        last_season,
+    }
+}
+
+#[rustfmt::skip]
+pub const LINEAGE_TEAMS: &[LineageTeam] = &[
+    team(11, "Atlanta Thrashers", "ATL", 35, 19992000, Some(20102011)),
+    team(27, "Phoenix Coyotes", "PHX", 28, 19961997, Some(20132014)),
+    team(31, "Minnesota North Stars", "MNS", 15, 19671968, Some(19921993)),
+    team(32, "Quebec Nordiques", "QUE", 27, 19791980, Some(19941995)),
+    team(33, "Winnipeg Jets (1979)", "WIN", 28, 19791980, Some(19951996)),
+    team(34, "Hartford Whalers", "HFD", 26, 19791980, Some(19961997)),
+    team(35, "Colorado Rockies", "CLR", 23, 19761977, Some(19811982)),
+    team(36, "Ottawa Senators (1917)", "SEN", 3, 19171918, Some(19331934)),
+    team(37, "Hamilton Tigers", "HAM", 4, 19201921, Some(19241925)),
+    team(38, "Pittsburgh Pirates", "PIR", 9, 19251926, Some(19291930)),
+    team(39, "Philadelphia Quakers", "QUA", 9, 19301931, Some(19301931)),
+    team(40, "Detroit Cougars", "DCG", 12, 19261927, Some(19291930)),
+    team(41, "Montreal Wanderers", "MWN", 2, 19171918, Some(19171918)),
+    team(42, "Quebec Bulldogs", "QBD", 4, 19191920, Some(19191920)),
+    team(43, "Montreal Maroons", "MMR", 7, 19241925, Some(19371938)),
+    team(44, "New York Americans", "NYA", 8, 19251926, Some(19401941)),
+    team(45, "St. Louis Eagles", "SLE", 3, 19341935, Some(19341935)),
+    team(46, "Oakland Seals", "OAK", 13, 19671968, Some(19691970)),
+    team(47, "Atlanta Flames", "AFM", 21, 19721973, Some(19791980)),
+    team(48, "Kansas City Scouts", "KCS", 23, 19741975, Some(19751976)),
+    team(49, "Cleveland Barons", "CLE", 13, 19761977, Some(19771978)),
+    team(50, "Detroit Falcons", "DFL", 12, 19301931, Some(19311932)),
+    team(51, "Brooklyn Americans", "BRK", 8, 19411942, Some(19411942)),
+    team(56, "California Golden Seals", "CGS", 13, 19701971, Some(19751976)),
+    team(57, "Toronto Arenas", "TAN", 5, 19171918, Some(19181919)),
+    team(58, "Toronto St. Patricks", "TSP", 5, 19191920, Some(19261927)),
+    team(59, "Utah Hockey Club", "UTA", 40, 20242025, None),
+];
+
+pub fn lineage_team(team_id: usize) -> Option<&'static LineageTeam> {
+    LINEAGE_TEAMS.iter().find(|team| team.id == team_id)
+}
+
+pub fn franchise_id(team_id: usize) -> Option<usize> {
+    teams::team_info(team_id)
+        .map(|team| team.franchise_id)
+        .or_else(|| lineage_team(team_id).map(|team| team.franchise_id))
+}
+
+pub fn franchise(team_id: usize) -> Option<&'static Franchise> {
+    FRANCHISES.get(&franchise_id(team_id)?)
+}
+
+/// The team the franchise plays as now, or last played as if it folded.
+pub fn current_team_id(team_id: usize) -> Option<usize> {
+    franchise(team_id).map(|franchise| franchise.most_recent_team_id)
+}
+
+/// The name of any team that has played in the league.
+pub fn team_name(team_id: usize) -> Option<&'static str> {
+    teams::team_info(team_id)
+        .map(|team| team.name.as_str())
+        .or_else(|| lineage_team(team_id).map(|team| team.name))
+}
+
+pub fn abbreviation(team_id: usize) -> Option<&'static str> {
+    teams::team_info(team_id)
+        .map(|team| team.abbreviation.as_str())
+        .or_else(|| lineage_team(team_id).map(|team| team.abbreviation))
+}
+
+pub fn same_franchise(team_id: usize, other_team_id: usize) -> bool {
+    team_id == other_team_id
+        || matches!(
+            (franchise_id(team_id), franchise_id(other_team_id)),
+            (Some(a), Some(b)) if a == b
+        )
+}
+
+/// Team ids a franchise has played under, oldest first, ending with the
+/// current team.
+pub fn lineage(franchise_id: usize) -> Vec<usize> {
+    let mut former: Vec<&LineageTeam> = LINEAGE_TEAMS
+        .iter()
+        .filter(|team| team.franchise_id == franchise_id)
+        .collect();
+    former.sort_by_key(|team| team.first_season);
+    former
+        .into_iter()
+        .map(|team| team.id)
+        .chain(
+            teams::TEAM_REGISTRY
+                .values()
+                .filter(|team| team.franchise_id == franchise_id)
+                .map(|team| team.id),
+        )
+        .collect()
+}
+
+/// Games grouped by the franchise of `team_id`'s opponent, so a team's
+/// history against the Nordiques and the Avalanche lands together.
+pub fn by_opponent_franchise(games: &[Game], team_id: usize) -> BTreeMap<usize, Vec<&Game>> {
+    let mut groups: BTreeMap<usize, Vec<&Game>> = BTreeMap::new();
+    for game in games {
+        let (home, away) = (game.teams.home.team.id, game.teams.away.team.id);
+        let opponent = if same_franchise(home, team_id) {
+            away
+        } else if same_franchise(away, team_id) {
+            home
+        } else {
+            continue;
+        };
+        if let Some(franchise_id) = franchise_id(opponent) {
+            groups.entry(franchise_id).or_default().push(game);
+        }
+    }
+    groups
+}
+
+/// Games between two franchises, given any team id from each.
+pub fn games_between(games: &[Game], team_id: usize, other_team_id: usize) -> Vec<&Game> {
+    games
+        .iter()
+        .filter(|game| {
+            let (home, away) = (game.teams.home.team.id, game.teams.away.team.id);
+            (same_franchise(home, team_id) && same_franchise(away, other_team_id))
+                || (same_franchise(away, team_id) && same_franchise(home, other_team_id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+
+    #[test]
+    fn test_relocated_teams() {
+        assert_eq!(team_name(11), Some("Atlanta Thrashers"));
+        assert_eq!(current_team_id(11), Some(teams::WINNIPEG_JETS_ID));
+        assert_eq!(current_team_id(34), Some(teams::CAROLINA_HURRICANES_ID));
+        assert_eq!(current_team_id(32), Some(teams::COLORADO_AVALANCHE_ID));
+        assert_eq!(team_name(59), Some("Utah Hockey Club"));
+        assert_eq!(teams::team_name(34), "Hartford Whalers");
+        // The original Jets are not the current ones.
+        assert!(!same_franchise(33, teams::WINNIPEG_JETS_ID));
+        assert!(same_franchise(33, teams::ARIZONA_COYOTES_ID));
+        assert_eq!(lineage(23), [48, 35, teams::NEW_JERSEY_DEVILS_ID]);
+        assert_eq!(lineage(12), [40, 50, teams::DETROIT_RED_WINGS_ID]);
+    }
+
+    #[test]
+    fn test_every_team_has_a_franchise() {
+        for team_id in teams::TEAM_REGISTRY
+            .keys()
+            .copied()
+            .chain(LINEAGE_TEAMS.iter().map(|team| team.id))
+        {
+            let franchise = franchise(team_id).unwrap();
+            assert!(team_name(franchise.most_recent_team_id).is_some());
+        }
+        assert_eq!(FRANCHISES.len(), 40);
+    }
+
+    #[test]
+    fn test_group_by_franchise() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let mut games = schedule.dates[0].games.clone();
+        // The same matchup from the Nordiques days.
+        let mut old = games[1].clone();
+        old.teams.away.team.id = 32;
+        old.teams.home.team.id = teams::BOSTON_BRUINS_ID;
+        let mut current = old.clone();
+        current.teams.away.team.id = teams::COLORADO_AVALANCHE_ID;
+        games.push(old);
+        games.push(current);
+
+        let groups = by_opponent_franchise(&games, teams::BOSTON_BRUINS_ID);
+        assert_eq!(groups[&27].len(), 2);
+        assert_eq!(groups[&1].len(), 1);
+        assert_eq!(games_between(&games, 32, teams::BOSTON_BRUINS_ID).len(), 2);
+    }
+}
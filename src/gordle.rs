@@ -0,0 +1,777 @@
+use crate::{
+    PlayerRecordData, FIVE_LETTER_LAST_NAMES, FOUR_LETTER_LAST_NAMES, SIX_LETTER_LAST_NAMES,
+};
+use anyhow::{bail, Error};
+use chrono::NaiveDate;
+use deunicode::deunicode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Tile {
+    Gray,
+    Yellow,
+    Green,
+}
+
+impl Tile {
+    /// `g` for green, `y` for yellow, `.`, `x` or `b` for gray.
+    pub fn from_char(c: char) -> Result<Self, Error> {
+        Ok(match c.to_ascii_lowercase() {
+            'g' => Tile::Green,
+            'y' => Tile::Yellow,
+            '.' | 'x' | 'b' => Tile::Gray,
+            _ => bail!("unknown tile '{}'", c),
+        })
+    }
+
+    /// The next color when a tile is clicked.
+    pub fn cycle(self) -> Self {
+        match self {
+            Tile::Gray => Tile::Yellow,
+            Tile::Yellow => Tile::Green,
+            Tile::Green => Tile::Gray,
+        }
+    }
+}
+
+pub fn parse_tiles(text: &str) -> Result<Vec<Tile>, Error> {
+    text.chars().map(Tile::from_char).collect()
+}
+
+fn letters(word: &str) -> Vec<char> {
+    word.chars().flat_map(char::to_lowercase).collect()
+}
+
+/// Scores one guess into `tiles` without allocating, so ranking can run
+/// it for every pair of names.
+fn score_into(guess: &[char], answer: &[char], tiles: &mut [Tile]) {
+    let mut used = [false; 32];
+    let answer = &answer[..answer.len().min(used.len())];
+    for (index, tile) in tiles.iter_mut().enumerate() {
+        *tile = if answer.get(index) == Some(&guess[index]) {
+            used[index] = true;
+            Tile::Green
+        } else {
+            Tile::Gray
+        };
+    }
+    for (index, letter) in guess.iter().enumerate() {
+        if tiles[index] == Tile::Green {
+            continue;
+        }
+        let unmatched = answer
+            .iter()
+            .enumerate()
+            .position(|(position, c)| !used[position] && c == letter);
+        if let Some(position) = unmatched {
+            used[position] = true;
+            tiles[index] = Tile::Yellow;
+        }
+    }
+}
+
+/// The tiles Wordle shows for `guess` when the answer is `answer`. Greens
+/// are placed first, then each remaining letter is yellow only while the
+/// answer still has an unmatched copy of it, left to right, so a repeated
+/// letter can come back yellow once and gray after.
+pub fn feedback(guess: &str, answer: &str) -> Vec<Tile> {
+    let guess = letters(guess);
+    let answer = letters(answer);
+    let mut tiles = vec![Tile::Gray; guess.len()];
+    score_into(&guess, &answer, &mut tiles);
+    tiles
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Guess {
+    pub word: String,
+    pub tiles: Vec<Tile>,
+}
+
+/// The name lengths with a built-in list.
+pub const WORD_LENGTHS: [usize; 3] = [4, 5, 6];
+
+pub const DEFAULT_WORD_LENGTH: usize = 5;
+
+/// The built-in names with `length` letters, or none for other lengths.
+pub fn last_names(length: usize) -> &'static [&'static str] {
+    match length {
+        4 => FOUR_LETTER_LAST_NAMES,
+        5 => FIVE_LETTER_LAST_NAMES,
+        6 => SIX_LETTER_LAST_NAMES,
+        _ => &[],
+    }
+}
+
+fn default_word_length() -> usize {
+    DEFAULT_WORD_LENGTH
+}
+
+/// Every guess made so far and the tiles shown for it. A name is still a
+/// candidate when guessing each word against it would have produced
+/// exactly the same tiles, which covers repeated letters, letter counts
+/// and positions ruled out by earlier guesses.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GordleState {
+    /// How many letters the answer has.
+    #[serde(default = "default_word_length")]
+    pub length: usize,
+    pub guesses: Vec<Guess>,
+}
+
+impl Default for GordleState {
+    fn default() -> Self {
+        Self::with_length(DEFAULT_WORD_LENGTH)
+    }
+}
+
+impl GordleState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_length(length: usize) -> Self {
+        Self {
+            length,
+            guesses: vec![],
+        }
+    }
+
+    pub fn guess(&mut self, word: &str, tiles: Vec<Tile>) -> Result<(), Error> {
+        let length = letters(word).len();
+        if length != tiles.len() {
+            bail!("{} has {} letters but {} tiles", word, length, tiles.len());
+        }
+        if length != self.length {
+            bail!("{} is not {} letters", word, self.length);
+        }
+        self.guesses.push(Guess {
+            word: word.to_string(),
+            tiles,
+        });
+        Ok(())
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        letters(name).len() == self.length
+            && self
+                .guesses
+                .iter()
+                .all(|guess| feedback(&guess.word, name) == guess.tiles)
+    }
+
+    pub fn candidates_from<'a>(&self, names: &[&'a str]) -> Vec<&'a str> {
+        names
+            .iter()
+            .copied()
+            .filter(|name| self.matches(name))
+            .collect()
+    }
+
+    /// The built-in names of the right length that fit every guess.
+    pub fn candidates(&self) -> Vec<&'static str> {
+        self.candidates_from(last_names(self.length))
+    }
+}
+
+/// A last name as it would be typed into Gordle: accents stripped and
+/// anything that isn't a letter dropped, so "O'Ree" is "ORee", "van
+/// Riemsdyk" is "vanRiemsdyk" and "Hašek" is "Hasek".
+pub fn word_from_last_name(last_name: &str) -> String {
+    deunicode(last_name)
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .collect()
+}
+
+/// Every distinct `length` letter word made from the last names in
+/// `records`, sorted case-insensitively.
+pub fn word_list(records: &PlayerRecordData, length: usize) -> Vec<String> {
+    let mut words: Vec<String> = records
+        .data
+        .iter()
+        .map(|record| word_from_last_name(&record.last_name))
+        .filter(|word| word.len() == length)
+        .collect();
+    words.sort_by_key(|word| (word.to_lowercase(), word.clone()));
+    words.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    words
+}
+
+/// `words` as a Rust array, ten to a line, like `FIVE_LETTER_LAST_NAMES`.
+pub fn word_list_source(name: &str, words: &[String]) -> String {
+    let mut source = format!("pub const {}: &[&str] = &[\n", name);
+    for line in words.chunks(10) {
+        let quoted: Vec<String> = line.iter().map(|word| format!("{:?}", word)).collect();
+        source.push_str(&format!("    {},\n", quoted.join(", ")));
+    }
+    source.push_str("];\n");
+    source
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ranking {
+    /// Most expected bits of information; higher scores are better.
+    Entropy,
+    /// Smallest largest group of names left over; lower scores are better.
+    WorstCase,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion<'a> {
+    pub word: &'a str,
+    pub score: f64,
+    /// Whether the word could itself be the answer.
+    pub candidate: bool,
+}
+
+fn pattern_code(tiles: &[Tile]) -> usize {
+    tiles.iter().fold(0, |code, tile| code * 3 + *tile as usize)
+}
+
+impl GordleState {
+    /// Scores every name of the right length in `names` as the next
+    /// guess, by how it would split the remaining candidates, and returns
+    /// the best `limit`. Ties go to names that could be the answer.
+    pub fn suggestions<'a>(
+        &self,
+        names: &[&'a str],
+        ranking: Ranking,
+        limit: usize,
+    ) -> Vec<Suggestion<'a>> {
+        let length = self.length;
+        let candidates: Vec<Vec<char>> = self
+            .candidates_from(names)
+            .iter()
+            .map(|name| letters(name))
+            .collect();
+        if candidates.is_empty() {
+            return vec![];
+        }
+        let total = candidates.len() as f64;
+        let mut tiles = vec![Tile::Gray; length];
+        let mut buckets = vec![0usize; 3usize.pow(length as u32)];
+
+        let mut suggestions: Vec<Suggestion<'a>> = names
+            .iter()
+            .filter_map(|name| {
+                let guess = letters(name);
+                if guess.len() != length {
+                    return None;
+                }
+                buckets.iter_mut().for_each(|count| *count = 0);
+                let mut candidate = false;
+                for answer in &candidates {
+                    candidate |= *answer == guess;
+                    score_into(&guess, answer, &mut tiles);
+                    buckets[pattern_code(&tiles)] += 1;
+                }
+                let score = match ranking {
+                    Ranking::Entropy => buckets
+                        .iter()
+                        .filter(|count| **count > 0)
+                        .map(|count| {
+                            let p = *count as f64 / total;
+                            -p * p.log2()
+                        })
+                        .sum(),
+                    Ranking::WorstCase => buckets.iter().copied().max().unwrap_or_default() as f64,
+                };
+                Some(Suggestion {
+                    word: name,
+                    score,
+                    candidate,
+                })
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| {
+            let by_score = match ranking {
+                Ranking::Entropy => b.score.partial_cmp(&a.score),
+                Ranking::WorstCase => a.score.partial_cmp(&b.score),
+            };
+            by_score
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.candidate.cmp(&a.candidate))
+                .then(a.word.cmp(b.word))
+        });
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+pub const MAX_GUESSES: usize = 6;
+
+/// The day of the first daily puzzle.
+fn first_day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2022, 11, 1).expect("first day")
+}
+
+/// Puzzle number for `date`, counting from 1 on the first day.
+pub fn puzzle_number(date: NaiveDate) -> i64 {
+    (date - first_day()).num_days() + 1
+}
+
+/// The names daily puzzles are drawn from, kept apart from
+/// `FIVE_LETTER_LAST_NAMES` so regenerating that list doesn't change past
+/// answers. Every day's answer depends on this list's length and order,
+/// so never reorder or remove a name here; to add names, start a new list
+/// and switch to it from a future date in `daily_answer`.
+pub const DAILY_ANSWERS: &[&str] = &[
+    "Aalto", "Aberg", "Acomb", "Acton", "Adams", "Agnew", "Ahcan", "Ahern", "Ahlin", "Ahola",
+    "Allan", "Allen", "Alley", "Allum", "Alves", "Armia", "Asham", "Ashby", "Aubin", "Aubry",
+    "Audet", "Auger", "Aulie", "Aulin", "Aurie", "Avery", "Awrey", "Ayres", "Babin", "Baird",
+    "Baker", "Balej", "Balon", "Banks", "Barbe", "Barch", "Baron", "Barry", "Bates", "Bathe",
+    "Bauer", "Bayda", "Beech", "Beers", "Begin", "Bekar", "Belak", "Belle", "Belov", "Benda",
+    "Berra", "Berry", "Berti", "Betik", "Betts", "Bicek", "Biega", "Bierk", "Biggs", "Biron",
+    "Bjork", "Black", "Blade", "Blair", "Blais", "Blake", "Blidh", "Block", "Bloom", "Bodak",
+    "Boddy", "Bodie", "Boehm", "Boldy", "Bonar", "Bonin", "Bonni", "Boone", "Booth", "Borer",
+    "Bossy", "Bouck", "Bouma", "Bowen", "Bower", "Bowey", "Boyce", "Boyer", "Boyes", "Boyko",
+    "Boyle", "Bozak", "Bozek", "Bozon", "Brady", "Bratt", "Braun", "Breen", "Brent", "Brine",
+    "Brink", "Britz", "Broda", "Broll", "Brome", "Brown", "Bruce", "Brule", "Bubla", "Bucyk",
+    "Budaj", "Bulis", "Burch", "Burke", "Burns", "Burry", "Buzek", "Byers", "Bykov", "Byram",
+    "Byron", "Cahan", "Carey", "Carle", "Carlo", "Caron", "Carse", "Casey", "Cates", "Chara",
+    "Chase", "Check", "Cibak", "Ciger", "Cisar", "Clark", "Cline", "Clowe", "Clune", "Cohen",
+    "Cooke", "Corsi", "Corso", "Corvo", "Cotch", "Coutu", "Cowan", "Cowen", "Cowie", "Coyle",
+    "Crabb", "Craig", "Crisp", "Cross", "Crowe", "Curry", "Cutta", "Dafoe", "Daley", "Dalpe",
+    "Danis", "Dansk", "Darby", "David", "Davie", "Davis", "Dawes", "DeLeo", "Debol", "Denis",
+    "Dewar", "Dietz", "Divis", "Djoos", "Doell", "Doran", "Dorey", "Doull", "Doyon", "Drake",
+    "Dries", "Druce", "Drury", "Duehr", "Dumba", "Dunne", "Dupre", "Duris", "Durno", "Durzi",
+    "Dwyer", "Eager", "Eakin", "Eaton", "Eaves", "Edler", "Egers", "Ehman", "Ekman", "Elias",
+    "Elich", "Eliot", "Eller", "Ellis", "Elomo", "Elson", "Emery", "Ennis", "Errey", "Esche",
+    "Evans", "Fahey", "Faksa", "Fasth", "Faulk", "Fauss", "Faust", "Fayne", "Fedun", "Fedyk",
+    "Felix", "Fiala", "Field", "Fiore", "Fiset", "Flett", "Flinn", "Flood", "Floyd", "Flynn",
+    "Focht", "Folco", "Foley", "Folin", "Foote", "Forey", "Fotiu", "Foudy", "Freer", "Fritz",
+    "Frost", "Fusco", "Gaetz", "Gagne", "Garon", "Gaume", "Gavey", "Gavin", "Geale", "Geran",
+    "Gerbe", "Gibbs", "Giles", "Gladu", "Glass", "Gloor", "Glynn", "Godin", "Gomez", "Goren",
+    "Gould", "Goyer", "Grant", "Greco", "Green", "Greer", "Gregg", "Greig", "Grier", "Gross",
+    "Gruen", "Gruhl", "Gryba", "Gudas", "Guhle", "Guite", "Guren", "Gusev", "Hagel", "Hague",
+    "Hajdu", "Hajek", "Halak", "Haley", "Halko", "Halmo", "Hamel", "Handy", "Hanna", "Hardy",
+    "Harju", "Harms", "Hasek", "Hauer", "Haula", "Hayek", "Hayes", "Healy", "Heath", "Hecht",
+    "Hedin", "Heidt", "Heins", "Hejda", "Henry", "Heron", "Hertl", "Hicke", "Hicks", "Himes",
+    "Hinse", "Hintz", "Hnidy", "Hodge", "Hoene", "Hogue", "Holan", "Holik", "Holos", "Holst",
+    "Holtz", "Honka", "Horak", "Hordy", "Horne", "Hossa", "Houck", "Houda", "Houde", "Hough",
+    "Houle", "Howse", "Hoyda", "Hrkac", "Huard", "Huber", "Hucul", "Huddy", "Hudon", "Hulse",
+    "Huras", "Hurme", "Hurst", "Huska", "Hyman", "Hynes", "Irmen", "Irvin", "Irwin", "Issel",
+    "James", "Janik", "Jaros", "Jarry", "Jarvi", "Jenik", "Jerwa", "Jirik", "Johns", "Jones",
+    "Joyal", "Joyce", "Jurco", "Juzda", "Kabel", "Kadri", "Kaese", "Kahun", "Kakko", "Kalus",
+    "Kampf", "Kanko", "Kapla", "Karpa", "Katic", "Keane", "Keans", "Keats", "Keefe", "Kehoe",
+    "Keith", "Kelly", "Kempe", "Kenny", "Kerch", "Kindl", "Kisio", "Klatt", "Klein", "Klemm",
+    "Klima", "Kloos", "Knott", "Kocur", "Koivu", "Konan", "Konik", "Kopak", "Korab", "Kowal",
+    "Kozak", "Kozun", "Kraft", "Krahn", "Krake", "Krebs", "Kreps", "Kromm", "Krook", "Krupp",
+    "Kruse", "Kukan", "Kulak", "Kulda", "Kunin", "Kuntz", "Kunyk", "Kurka", "Kurri", "Kurtz",
+    "Kuzyk", "Kwong", "Kyrou", "LaDue", "Labbe", "Labre", "Laich", "Laine", "Laing", "Laird",
+    "Lalor", "Lamby", "Latal", "Latos", "Latta", "Lauen", "Lauer", "Lazar", "Leach", "Leahy",
+    "Lebda", "Leddy", "Ledin", "Leduc", "Leger", "Legge", "Lehto", "Leier", "Leino", "Leivo",
+    "Lemay", "Lesuk", "Lever", "Levie", "Lewis", "Libby", "Liles", "Lilja", "Lipon", "Lisin",
+    "Loach", "Locas", "Locke", "Logan", "Lojek", "Loney", "Lowry", "Loyns", "Lucas", "Lucic",
+    "Luksa", "Lumme", "Lunde", "Lundy", "Luoma", "Luoto", "Lupul", "Lynch", "Lyons", "Lysak",
+    "Macey", "Magee", "Maggs", "Major", "Makar", "Malec", "Maley", "Malik", "Mamin", "Manno",
+    "March", "Marha", "Mario", "Marks", "Marsh", "Maruk", "Mason", "Matte", "Mayer", "Mazur",
+    "McKay", "McKee", "McKim", "McNab", "McRae", "Meech", "Meeke", "Megan", "Meger", "Megna",
+    "Meier", "Melin", "Meyer", "Mezei", "Miehm", "Miele", "Migay", "Mikol", "Milks", "Mills",
+    "Miner", "Minor", "Modin", "Modry", "Moger", "Moher", "Mohns", "Molin", "Moore", "Moran",
+    "Morin", "Moser", "Motin", "Motte", "Moxey", "Mozik", "Munro", "Musil", "Myers", "Myles",
+    "Nanne", "Necas", "Neely", "Nevin", "Niemi", "Nieto", "Nigro", "Nilan", "Noble", "Nolan",
+    "Nolet", "Noris", "Nosek", "Novak", "Nowak", "Nurse", "Nyrop", "Oates", "Oberg",
+    "Obsut", "Oduya", "Olesz", "Oliwa", "Olsen", "Olson", "Olver", "Omark", "Orban", "Orlov",
+    "Orpik", "Osala", "Oshie", "Palat", "Panik", "Pardy", "Parks", "Parro", "Parse", "Pasek",
+    "Pasin", "Patey", "Payer", "Payne", "Peake", "Pedan", "Peeke", "Pelyk", "Percy", "Perry",
+    "Pesce", "Pesut", "Petan", "Petit", "Petry", "Phair", "Pilar", "Pilon", "Pilut", "Pinho",
+    "Pinto", "Pionk", "Piros", "Pirri", "Pirus", "Pitre", "Pivko", "Platt", "Pleau", "Plett",
+    "Plumb", "Pocza", "Poeta", "Poile", "Point", "Polak", "Polis", "Powis", "Pratt", "Price",
+    "Propp", "Prout", "Prpic", "Prust", "Pryor", "Pudas", "Puppa", "Pusie", "Pyatt", "Pysyk",
+    "Quick", "Quine", "Quinn", "Quint", "Radil", "Raffl", "Rallo", "Ralph", "Ranta", "Raska",
+    "Ready", "Reeds", "Reedy", "Reese", "Regan", "Regin", "Reich", "Reitz", "Repik", "Resch",
+    "Ricci", "Riley", "Rinne", "Rioux", "Rivet", "Roach", "Robak", "Roche", "Rodin", "Roest",
+    "Rolfe", "Ronan", "Ronty", "Rosen", "Rossi", "Roupe", "Rouse", "Royer", "Runge", "Russo",
+    "Rutta", "Ruutu", "Ryder", "Sabol", "Sacco", "Sakic", "Salei", "Samis", "Sands", "Sarno",
+    "Satan", "Sauer", "Sauve", "Sbisa", "Scott", "Sedin", "Segal", "Sejba", "Sejna", "Sekac",
+    "Selby", "Semak", "Semin", "Seney", "Seppa", "Shack", "Shand", "Shank", "Sharp", "Sherf",
+    "Shero", "Shill", "Shmyr", "Shore", "Short", "Shugg", "Shutt", "Simek", "Simon", "Siren",
+    "Sislo", "Sivek", "Skjei", "Slegr", "Sloan", "Smaby", "Smail", "Smart", "Smith", "Smrek",
+    "Smrke", "Smyth", "Sneep", "Snell", "Somik", "Sopel", "Soucy", "Speck", "Speer", "Srsen",
+    "Staal", "Starr", "Steel", "Steen", "Stern", "Stock", "Stoll", "Stone", "Storm", "Storr",
+    "Sturm", "Suchy", "Suess", "Sulak", "Suomi", "Surma", "Sustr", "Suter", "Suzor", "Swain",
+    "Sydor", "Sykes", "Szura", "Taffe", "Takko", "Tamer", "Tanev", "Tanti", "Tatar", "Terry",
+    "Teves", "Thang", "Thoms", "Thyer", "Tichy", "Tidey", "Tiley", "Titov", "Toews", "Trapp",
+    "Traub", "Tripp", "Trnka", "Tropp", "Tudin", "Tudor", "Tufte", "Turco", "Turek", "Tuten",
+    "Twist", "Tynan", "Ulmer", "Unger", "Urbom", "Vaive", "Vanek", "Varis", "Vaske", "Vasko",
+    "Verot", "Vesce", "Vesey", "Virta", "Vokes", "Volek", "Vopat", "Voros", "Vrana", "Waite",
+    "Walsh", "Wares", "Watts", "Weber", "Wedin", "Weeks", "Weise", "Weiss", "Welch", "Wells",
+    "Welsh", "White", "Whyte", "Wiebe", "Wiley", "Wilks", "Wiste", "Woods", "Wylie", "Wyman",
+    "Yates", "Yelle", "Young", "Zacha", "Zaine", "Zajac", "Zanon", "Zezel", "Zizka", "Zombo",
+    "Zubov", "Zykov",
+];
+
+/// The answer for `date`. Day numbers are scrambled with SplitMix64 so
+/// consecutive days don't walk through the list alphabetically, but every
+/// player gets the same name on the same day.
+pub fn daily_answer(date: NaiveDate) -> &'static str {
+    let mut x = (puzzle_number(date) as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    DAILY_ANSWERS[(x % DAILY_ANSWERS.len() as u64) as usize]
+}
+
+/// One day's puzzle in progress, saved in the browser between visits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyGame {
+    pub date: NaiveDate,
+    pub guesses: Vec<String>,
+}
+
+impl DailyGame {
+    pub fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            guesses: vec![],
+        }
+    }
+
+    pub fn answer(&self) -> &'static str {
+        daily_answer(self.date)
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.guesses
+            .last()
+            .is_some_and(|guess| guess.eq_ignore_ascii_case(self.answer()))
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.guesses.len() >= MAX_GUESSES
+    }
+
+    /// Checks `word` against the name list and records it, returning its
+    /// tiles.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<Tile>, Error> {
+        if self.is_over() {
+            bail!("today's puzzle is over");
+        }
+        let word = word.trim();
+        let length = letters(self.answer()).len();
+        let name = last_names(length)
+            .iter()
+            .chain(DAILY_ANSWERS)
+            .find(|name| name.eq_ignore_ascii_case(word));
+        let name = match name {
+            Some(name) => *name,
+            None if word.chars().count() != length => bail!("names have {} letters", length),
+            None => bail!("{} isn't in the list of player names", word),
+        };
+        self.guesses.push(name.to_string());
+        Ok(feedback(name, self.answer()))
+    }
+
+    pub fn rows(&self) -> Vec<(&str, Vec<Tile>)> {
+        self.guesses
+            .iter()
+            .map(|guess| (guess.as_str(), feedback(guess, self.answer())))
+            .collect()
+    }
+
+    /// Colored squares only, safe to post without giving the name away.
+    pub fn share_text(&self) -> String {
+        let score = if self.is_won() {
+            self.guesses.len().to_string()
+        } else {
+            "X".to_string()
+        };
+        let mut text = format!(
+            "Gordle {} {}/{}\n",
+            puzzle_number(self.date),
+            score,
+            MAX_GUESSES
+        );
+        for (_, tiles) in self.rows() {
+            text.extend(tiles.iter().map(|tile| match tile {
+                Tile::Green => '🟩',
+                Tile::Yellow => '🟨',
+                Tile::Gray => '⬜',
+            }));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GordleStats {
+    pub played: usize,
+    pub won: usize,
+    pub current_streak: usize,
+    pub max_streak: usize,
+    /// Wins by number of guesses, one through six.
+    pub distribution: [usize; MAX_GUESSES],
+    pub last_played: Option<NaiveDate>,
+    pub last_won: Option<NaiveDate>,
+}
+
+impl GordleStats {
+    /// Counts a finished game once. A streak carries on only from a win
+    /// the day before.
+    pub fn record(&mut self, game: &DailyGame) {
+        if !game.is_over() || self.last_played.is_some_and(|day| day >= game.date) {
+            return;
+        }
+        self.played += 1;
+        self.last_played = Some(game.date);
+        if game.is_won() {
+            self.won += 1;
+            // Saved games come back from the browser, so don't trust the
+            // count to fit.
+            let guesses = game.guesses.len().clamp(1, MAX_GUESSES);
+            self.distribution[guesses - 1] += 1;
+            let continues = self
+                .last_won
+                .is_some_and(|day| game.date.pred_opt() == Some(day));
+            self.current_streak = if continues {
+                self.current_streak + 1
+            } else {
+                1
+            };
+            self.max_streak = self.max_streak.max(self.current_streak);
+            self.last_won = Some(game.date);
+        } else {
+            self.current_streak = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn state(guesses: &[(&str, &str)]) -> GordleState {
+        let mut state = GordleState::new();
+        for (word, tiles) in guesses {
+            state.guess(word, parse_tiles(tiles).unwrap()).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_feedback_with_repeated_letters() {
+        // One 'a' in the answer: the first is yellow, the second gray.
+        assert_eq!(feedback("Allan", "Lalor"), parse_tiles("yyg..").unwrap());
+        // A green takes the only 'e' before an earlier copy can be yellow.
+        assert_eq!(feedback("Eller", "Bauer"), parse_tiles("...gg").unwrap());
+        // Two 'e's in the answer, so the unplaced one is yellow.
+        assert_eq!(feedback("Eller", "Meier"), parse_tiles("y..gg").unwrap());
+        // Two 'l's in the answer, one guessed in place.
+        assert_eq!(feedback("Lalor", "Allan"), parse_tiles("yyg..").unwrap());
+        assert_eq!(feedback("Staal", "Staal"), parse_tiles("ggggg").unwrap());
+    }
+
+    #[test]
+    fn test_yellow_and_gray_for_the_same_letter() {
+        let state = state(&[("Allan", "yyg..")]);
+        let candidates = state.candidates();
+        assert!(candidates.contains(&"Lalor"));
+        for name in &candidates {
+            let name = name.to_lowercase();
+            // One 'a', not first; two 'l's, one third and the other not second.
+            assert_eq!(name.matches('a').count(), 1);
+            assert_eq!(name.matches('l').count(), 2);
+            assert_ne!(&name[0..1], "a");
+            assert_ne!(&name[1..2], "l");
+            assert_eq!(&name[2..3], "l");
+        }
+    }
+
+    #[test]
+    fn test_gray_repeat_caps_the_count() {
+        // The green 'e' is the only one, so the gray first 'e' rules out
+        // names with a second 'e' anywhere.
+        let state = state(&[("Eller", "...gg")]);
+        let candidates = state.candidates();
+        assert!(candidates.contains(&"Bauer"));
+        assert!(!candidates.contains(&"Meier"));
+        assert!(!candidates.contains(&"Greer"));
+        for name in &candidates {
+            assert_eq!(name.to_lowercase().matches('e').count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_guesses_accumulate() {
+        let one = state(&[("Adams", "....y")]);
+        let two = state(&[("Adams", "....y"), ("Scott", "y...g")]);
+        assert!(two.candidates().len() < one.candidates().len());
+        assert!(two.matches("Pesut"));
+        for name in two.candidates() {
+            let name = name.to_lowercase();
+            // 's' is in the name but neither first nor last, and the only
+            // 't' is last.
+            assert!(name.contains('s'));
+            assert!(!name.starts_with('s') && !name.ends_with('s'));
+            assert!(name.ends_with('t'));
+            assert_eq!(name.matches('t').count(), 1);
+            assert!(!name.contains(|c| "admco".contains(c)));
+        }
+    }
+
+    #[test]
+    fn test_suggestions_split_candidates() {
+        let names = ["Hertl", "Hintz", "Hicks", "Sharp", "Adams"];
+        let state = state(&[("Adams", ".....")]);
+        assert_eq!(state.candidates_from(&names), ["Hertl", "Hintz"]);
+
+        // Either candidate tells the two apart, so they win the tie with
+        // words that can't be the answer.
+        let best = state.suggestions(&names, Ranking::Entropy, 3);
+        assert_eq!(best[0].word, "Hertl");
+        assert!((best[0].score - 1.0).abs() < 1e-9);
+        assert!(best[0].candidate);
+        assert_eq!(best[1].word, "Hintz");
+
+        let worst_case = state.suggestions(&names, Ranking::WorstCase, 5);
+        assert_eq!(worst_case[0].score, 1.0);
+        assert_eq!(worst_case.last().unwrap().score, 2.0);
+    }
+
+    #[test]
+    fn test_suggestions_rank_the_whole_list() {
+        let state = GordleState::new();
+        let all = state.suggestions(FIVE_LETTER_LAST_NAMES, Ranking::Entropy, usize::MAX);
+        assert_eq!(all.len(), FIVE_LETTER_LAST_NAMES.len());
+        assert!(all.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        // A good opener splits the list far better than a name of rare
+        // letters does.
+        let zizka = all.iter().find(|s| s.word == "Zizka").unwrap();
+        assert!(all[0].score > zizka.score + 1.0);
+    }
+
+    #[test]
+    fn test_word_list() {
+        let records: PlayerRecordData =
+            serde_json::from_str(include_str!("../data/fixtures/player-records.json")).unwrap();
+        assert_eq!(word_list(&records, 5), ["Hasek", "Laine", "Sedin"]);
+        assert_eq!(word_list(&records, 4), ["Hull", "ORee"]);
+        assert_eq!(word_list(&records, 6), ["Dahlin"]);
+        assert_eq!(word_list(&records, 7), ["StLouis", "Stutzle"]);
+        assert_eq!(word_list(&records, 11), ["vanRiemsdyk"]);
+        assert_eq!(word_list(&records, 12), ["EkmanLarsson"]);
+        assert_eq!(
+            word_list_source("FOUR_LETTER_LAST_NAMES", &word_list(&records, 4)),
+            "pub const FOUR_LETTER_LAST_NAMES: &[&str] = &[\n    \"Hull\", \"ORee\",\n];\n"
+        );
+    }
+
+    #[test]
+    fn test_built_in_lists_are_normalized() {
+        for length in WORD_LENGTHS {
+            for name in last_names(length) {
+                assert_eq!(word_from_last_name(name), *name);
+                assert_eq!(name.len(), length);
+            }
+        }
+    }
+
+    #[test]
+    fn test_other_lengths() {
+        let mut state = GordleState::with_length(4);
+        assert!(state.guess("Adams", parse_tiles(".....").unwrap()).is_err());
+        state.guess("Hull", parse_tiles("g.gg").unwrap()).unwrap();
+        assert_eq!(state.candidates(), ["Hall", "Holl"]);
+
+        let state = GordleState::with_length(6);
+        let best = state.suggestions(SIX_LETTER_LAST_NAMES, Ranking::Entropy, 1);
+        assert_eq!(best[0].word.len(), 6);
+        assert_eq!(state.candidates().len(), SIX_LETTER_LAST_NAMES.len());
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, 11, day).unwrap()
+    }
+
+    #[test]
+    fn test_daily_answer() {
+        assert_eq!(puzzle_number(day(1)), 1);
+        // Answers already played must never change.
+        assert_eq!(daily_answer(day(1)), "Jarry");
+        assert_eq!(daily_answer(day(20)), "Bower");
+        assert_eq!(
+            daily_answer(NaiveDate::from_ymd_opt(2023, 6, 15).unwrap()),
+            "Roche"
+        );
+        let week: HashSet<&str> = (1..=7).map(|d| daily_answer(day(d))).collect();
+        assert!(week.len() > 5);
+    }
+
+    #[test]
+    fn test_daily_game() {
+        let mut game = DailyGame::new(day(20));
+        let answer = game.answer();
+        assert!(game.guess("Zzzzz").is_err());
+        assert!(game.guess("Hall").is_err());
+        assert!(game.guesses.is_empty());
+
+        let wrong = if answer == "Adams" { "Hertl" } else { "Adams" };
+        game.guess(&wrong.to_lowercase()).unwrap();
+        assert_eq!(game.guesses, [wrong]);
+        assert_eq!(game.guess(answer).unwrap(), parse_tiles("ggggg").unwrap());
+        assert!(game.is_won() && game.is_over());
+        assert!(game.guess(wrong).is_err());
+
+        let share = game.share_text();
+        assert!(share.starts_with("Gordle 20 2/6\n"));
+        assert!(share.ends_with("🟩🟩🟩🟩🟩\n"));
+        assert!(!share.contains(answer));
+    }
+
+    #[test]
+    fn test_stats_streaks() {
+        let won = |d: u32| {
+            let mut game = DailyGame::new(day(d));
+            game.guess(game.answer()).unwrap();
+            game
+        };
+        let lost = |d: u32| {
+            let mut game = DailyGame::new(day(d));
+            let wrong = if game.answer() == "Adams" {
+                "Hertl"
+            } else {
+                "Adams"
+            };
+            for _ in 0..MAX_GUESSES {
+                game.guess(wrong).unwrap();
+            }
+            game
+        };
+        let mut stats = GordleStats::default();
+        stats.record(&won(1));
+        stats.record(&won(1));
+        stats.record(&won(2));
+        assert_eq!((stats.played, stats.current_streak), (2, 2));
+        stats.record(&lost(3));
+        assert_eq!(stats.current_streak, 0);
+        stats.record(&won(5));
+        assert_eq!((stats.current_streak, stats.max_streak), (1, 2));
+        assert_eq!(stats.distribution[0], 3);
+        assert_eq!((stats.played, stats.won), (4, 3));
+
+        // A saved game edited to have too many guesses still counts.
+        let mut tampered = won(6);
+        tampered.guesses = vec![tampered.answer().to_string(); MAX_GUESSES + 3];
+        stats.record(&tampered);
+        assert_eq!(stats.distribution[MAX_GUESSES - 1], 1);
+    }
+
+    #[test]
+    fn test_guess_length_mismatch() {
+        let mut state = GordleState::new();
+        assert!(state.guess("Adams", parse_tiles("....").unwrap()).is_err());
+        assert!(parse_tiles("gyz..").is_err());
+        state.guess("Adams", parse_tiles(".....").unwrap()).unwrap();
+        assert!(state.guess("Hall", parse_tiles("....").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{franchise, locale::Locale, season::Season, Game};
+use std::collections::BTreeMap;
+
+/// One side's results against the other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: usize,
+    pub losses: usize,
+    /// Losses in overtime or a shootout, not counted in `losses`.
+    pub ot_losses: usize,
+    pub goals_for: usize,
+    pub goals_against: usize,
+}
+
+impl Record {
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.ot_losses
+    }
+
+    fn add(&mut self, game: &Game, team_id: usize) {
+        let (us, them) = if franchise::same_franchise(game.teams.home.team.id, team_id) {
+            (&game.teams.home, &game.teams.away)
+        } else {
+            (&game.teams.away, &game.teams.home)
+        };
+        self.goals_for += us.score;
+        self.goals_against += them.score;
+        if us.score > them.score {
+            self.wins += 1;
+        } else if game.went_to_overtime() && !game.is_playoffs() {
+            self.ot_losses += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+
+    /// "12-9-3"
+    pub fn summary(&self) -> String {
+        format!("{}-{}-{}", self.wins, self.losses, self.ot_losses)
+    }
+}
+
+/// The season a game belongs to, from the API when it says.
+pub fn season_of(game: &Game) -> Season {
+    Season::from_id(&game.season).unwrap_or_else(|| Season::for_date(game.game_date.date_naive()))
+}
+
+fn counts(game: &Game) -> bool {
+    game.is_finished() && (game.is_regular_season() || game.is_playoffs())
+}
+
+/// Every finished regular season and playoff meeting between two
+/// franchises, from `team_id`'s side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadToHead {
+    pub team_id: usize,
+    pub other_team_id: usize,
+    pub overall: Record,
+    pub playoffs: Record,
+    pub by_season: BTreeMap<Season, Record>,
+}
+
+impl HeadToHead {
+    pub fn new(games: &[Game], team_id: usize, other_team_id: usize) -> Self {
+        let mut head_to_head = Self {
+            team_id,
+            other_team_id,
+            overall: Record::default(),
+            playoffs: Record::default(),
+            by_season: BTreeMap::new(),
+        };
+        for game in franchise::games_between(games, team_id, other_team_id) {
+            if !counts(game) {
+                continue;
+            }
+            head_to_head.overall.add(game, team_id);
+            if game.is_playoffs() {
+                head_to_head.playoffs.add(game, team_id);
+            }
+            head_to_head
+                .by_season
+                .entry(season_of(game))
+                .or_default()
+                .add(game, team_id);
+        }
+        head_to_head
+    }
+
+    pub fn to_text(&self) -> String {
+        let name = |team_id| franchise::team_name(team_id).unwrap_or("?");
+        let mut text = format!(
+            "{} vs {}: {} ({} GF, {} GA)\n",
+            name(self.team_id),
+            name(self.other_team_id),
+            self.overall.summary(),
+            self.overall.goals_for,
+            self.overall.goals_against
+        );
+        if self.playoffs.games() > 0 {
+            text.push_str(&format!("Playoffs: {}\n", self.playoffs.summary()));
+        }
+        for (season, record) in self.by_season.iter().rev() {
+            text.push_str(&format!("{}: {}\n", season.label(), record.summary()));
+        }
+        text
+    }
+}
+
+/// Wins for each side in a game's regular season series so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeasonSeries {
+    pub away_team_id: usize,
+    pub home_team_id: usize,
+    pub away_wins: usize,
+    pub home_wins: usize,
+}
+
+impl SeasonSeries {
+    /// Meetings earlier in `game`'s season among `games`, or `None` if this
+    /// is their first.
+    pub fn before(game: &Game, games: &[Game]) -> Option<Self> {
+        if !game.is_regular_season() {
+            return None;
+        }
+        let season = season_of(game);
+        let (away_team_id, home_team_id) = (game.teams.away.team.id, game.teams.home.team.id);
+        let mut series = Self {
+            away_team_id,
+            home_team_id,
+            away_wins: 0,
+            home_wins: 0,
+        };
+        for earlier in franchise::games_between(games, away_team_id, home_team_id) {
+            if earlier.game_pk == game.game_pk
+                || earlier.game_date >= game.game_date
+                || !earlier.is_finished()
+                || !earlier.is_regular_season()
+                || season_of(earlier) != season
+            {
+                continue;
+            }
+            if franchise::same_franchise(earlier.winner(), away_team_id) {
+                series.away_wins += 1;
+            } else {
+                series.home_wins += 1;
+            }
+        }
+        (series.away_wins + series.home_wins > 0).then_some(series)
+    }
+
+    /// "SJS leads season series 2-1"
+    pub fn describe(&self) -> String {
+        self.describe_in(Locale::English)
+    }
+
+    pub fn describe_in(&self, locale: Locale) -> String {
+        let abbreviation = |team_id| franchise::abbreviation(team_id).unwrap_or("?");
+        let (leader, wins, losses) = if self.away_wins >= self.home_wins {
+            (self.away_team_id, self.away_wins, self.home_wins)
+        } else {
+            (self.home_team_id, self.home_wins, self.away_wins)
+        };
+        match (locale, wins == losses) {
+            (Locale::English, true) => format!("Season series tied {}-{}", wins, losses),
+            (Locale::English, false) => format!(
+                "{} leads season series {}-{}",
+                abbreviation(leader),
+                wins,
+                losses
+            ),
+            (Locale::French, true) => format!("Série de la saison égale {}-{}", wins, losses),
+            (Locale::French, false) => format!(
+                "{} mène la série de la saison {}-{}",
+                abbreviation(leader),
+                wins,
+                losses
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{teams, NextGameSchedule};
+    use chrono::Duration;
+
+    fn meeting(
+        template: &Game,
+        game_pk: usize,
+        days_ago: i64,
+        away: usize,
+        home: usize,
+        scores: (usize, usize),
+    ) -> Game {
+        let mut game = template.clone();
+        game.game_pk = game_pk;
+        game.game_date = template.game_date - Duration::days(days_ago);
+        game.teams.away.team.id = away;
+        game.teams.home.team.id = home;
+        game.teams.away.score = scores.0;
+        game.teams.home.score = scores.1;
+        game.linescore.current_period = 3;
+        game
+    }
+
+    #[test]
+    fn test_season_series() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let (sjs, edm) = (teams::SAN_JOSE_SHARKS_ID, teams::EDMONTON_OILERS_ID);
+        let finished = schedule.dates[0].games[0].clone();
+        let mut games = vec![
+            meeting(&finished, 1, 30, sjs, edm, (4, 2)),
+            meeting(&finished, 2, 20, edm, sjs, (1, 3)),
+            meeting(&finished, 3, 10, sjs, edm, (2, 5)),
+            // Last season doesn't count toward this one.
+            meeting(&finished, 4, 300, edm, sjs, (6, 0)),
+        ];
+        games[3].season = "20212022".to_string();
+        let mut tonight = meeting(&finished, 5, 0, sjs, edm, (0, 0));
+        tonight.status = schedule.dates[0].games[4].status.clone();
+        games.push(tonight.clone());
+
+        let series = SeasonSeries::before(&tonight, &games).unwrap();
+        assert_eq!((series.away_wins, series.home_wins), (2, 1));
+        assert_eq!(series.describe(), "SJS leads season series 2-1");
+        assert_eq!(
+            series.describe_in(Locale::French),
+            "SJS mène la série de la saison 2-1"
+        );
+
+        let head_to_head = HeadToHead::new(&games, edm, sjs);
+        assert_eq!(head_to_head.overall.summary(), "2-2-0");
+        assert_eq!(head_to_head.by_season.len(), 2);
+        assert_eq!(head_to_head.overall.goals_for, 14);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    locale::{Label, Locale},
+    teams, Game,
+};
+use chrono::{Datelike, NaiveDate};
+
+/// Why a past result is worth showing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Notable {
+    Playoffs,
+    Favorite,
+    Overtime,
+    Blowout,
+    HighScoring,
+}
+
+impl Notable {
+    pub fn label(&self) -> Label {
+        match self {
+            Notable::Playoffs => Label::Playoffs,
+            Notable::Favorite => Label::Favorite,
+            Notable::Overtime => Label::WentToOvertime,
+            Notable::Blowout => Label::Blowout,
+            Notable::HighScoring => Label::HighScoring,
+        }
+    }
+}
+
+const BLOWOUT_MARGIN: usize = 5;
+const HIGH_SCORING_TOTAL: usize = 11;
+
+/// The same calendar date in each of the `years` years before `date`,
+/// most recent first. February 29 only shows up in leap years.
+pub fn this_day_dates(date: NaiveDate, years: usize) -> Vec<NaiveDate> {
+    (1..=years as i32)
+        .filter_map(|back| NaiveDate::from_ymd_opt(date.year() - back, date.month(), date.day()))
+        .collect()
+}
+
+pub fn notable_reasons(game: &Game) -> Vec<Notable> {
+    let mut reasons = vec![];
+    if !game.is_finished() {
+        return reasons;
+    }
+    let (away, home) = (game.teams.away.score, game.teams.home.score);
+    if game.is_playoffs() {
+        reasons.push(Notable::Playoffs);
+    }
+    if teams::FAVORITE_TEAM_IDS
+        .iter()
+        .any(|team_id| game.has_competitor(*team_id))
+    {
+        reasons.push(Notable::Favorite);
+    }
+    if game.went_to_overtime() {
+        reasons.push(Notable::Overtime);
+    }
+    if away.abs_diff(home) >= BLOWOUT_MARGIN {
+        reasons.push(Notable::Blowout);
+    }
+    if away + home >= HIGH_SCORING_TOTAL {
+        reasons.push(Notable::HighScoring);
+    }
+    reasons
+}
+
+/// Finished games with something to say about them, playoff games first.
+pub fn notable_results(games: &[Game]) -> Vec<(&Game, Vec<Notable>)> {
+    let mut results: Vec<(&Game, Vec<Notable>)> = games
+        .iter()
+        .map(|game| (game, notable_reasons(game)))
+        .filter(|(_, reasons)| !reasons.is_empty())
+        .collect();
+    results.sort_by(|(_, a), (_, b)| a.cmp(b));
+    results
+}
+
+/// "Edmonton Oilers 3 @ San Jose Sharks 4 (OT)"
+pub fn describe_result(game: &Game, locale: Locale) -> String {
+    let result = format!(
+        "{} {} @ {} {}",
+        game.teams.away.team.display_name(),
+        game.teams.away.score,
+        game.teams.home.team.display_name(),
+        game.teams.home.score,
+    );
+    if game.went_to_overtime() {
+        format!("{} ({})", result, locale.text(Label::Overtime))
+    } else {
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+
+    #[test]
+    fn test_this_day_dates() {
+        let leap_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(
+            this_day_dates(leap_day, 8),
+            [
+                NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(),
+                NaiveDate::from_ymd_opt(2016, 2, 29).unwrap()
+            ]
+        );
+        assert_eq!(
+            this_day_dates(NaiveDate::from_ymd_opt(2022, 11, 20).unwrap(), 3).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_notable_results() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let mut games = schedule.dates[0].games.clone();
+        // An old Whalers game the API names only by id.
+        games[1].game_type = "P".to_string();
+        games[1].teams.away.team.id = 34;
+        games[1].teams.away.team.name = String::new();
+        games[1].teams.away.score = 9;
+
+        let results = notable_results(&games);
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].1,
+            [Notable::Playoffs, Notable::Blowout, Notable::HighScoring]
+        );
+        assert_eq!(
+            describe_result(results[0].0, Locale::English),
+            "Hartford Whalers 9 @ Montréal Canadiens 2"
+        );
+        assert_eq!(results[1].1, [Notable::Favorite, Notable::Overtime]);
+        assert_eq!(
+            describe_result(results[1].0, Locale::English),
+            "Edmonton Oilers 3 @ San Jose Sharks 4 (OT)"
+        );
+        assert_eq!(
+            describe_result(results[1].0, Locale::French),
+            "Edmonton Oilers 3 @ San Jose Sharks 4 (prol.)"
+        );
+        assert_eq!(games[1].game_type_label(), Some(Label::Playoffs));
+        assert_eq!(games[0].game_type_label(), None);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{teams, Game, GameState};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+const PRODUCT_ID: &str = "-//games_today//NHL Games Today//EN";
+const UID_DOMAIN: &str = "games-today";
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Which games to include in an export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameFilter {
+    All,
+    Teams(Vec<usize>),
+}
+
+impl GameFilter {
+    pub fn team(team_id: usize) -> Self {
+        GameFilter::Teams(vec![team_id])
+    }
+
+    pub fn favorites() -> Self {
+        GameFilter::Teams(teams::FAVORITE_TEAM_IDS.to_vec())
+    }
+
+    pub fn matches(&self, game: &Game) -> bool {
+        match self {
+            GameFilter::All => true,
+            GameFilter::Teams(team_ids) => team_ids.iter().any(|id| game.has_competitor(*id)),
+        }
+    }
+}
+
+/// Games don't publish an end time, so block out a typical game length,
+/// with a little extra for the playoffs where overtime runs long.
+pub fn estimated_duration(game: &Game) -> Duration {
+    if game.game_type == "P" {
+        Duration::hours(3)
+    } else {
+        Duration::minutes(150)
+    }
+}
+
+/// Builds a VCALENDAR with one VEVENT per game matching `filter`.
+/// `previews` holds broadcaster strings keyed by `game_pk`, as shown
+/// on the web page.
+pub fn calendar(
+    games: &[Game],
+    previews: &HashMap<usize, String>,
+    filter: &GameFilter,
+    now: DateTime<Utc>,
+) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    for game in games.iter().filter(|game| filter.matches(game)) {
+        lines.extend(event(game, previews.get(&game.game_pk), now));
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn event(game: &Game, preview: Option<&String>, now: DateTime<Utc>) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}@{}", game.game_pk, UID_DOMAIN),
+        format!("DTSTAMP:{}", timestamp(now)),
+    ];
+    if game.is_tbd() {
+        // Start times that haven't been announced get an all day event on
+        // the scheduled date, which the league publishes in Eastern time.
+        let date = game
+            .game_date
+            .with_timezone(&chrono_tz::America::New_York)
+            .date_naive();
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+    } else {
+        lines.push(format!("DTSTART:{}", timestamp(game.game_date)));
+        lines.push(format!(
+            "DTEND:{}",
+            timestamp(game.game_date + estimated_duration(game))
+        ));
+    }
+    lines.push(format!("SUMMARY:{}", escape(&summary(game))));
+    if let Some(venue) = teams::team_info(game.teams.home.team.id).map(|team| &team.venue) {
+        lines.push(format!("LOCATION:{}", escape(&venue.location())));
+    }
+    if let Some(preview) = preview.filter(|preview| !preview.is_empty()) {
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            escape(&format!("Broadcasters: {}", preview))
+        ));
+    }
+    let status = match game.state() {
+        GameState::Postponed => "CANCELLED",
+        GameState::Preview if game.is_tbd() => "TENTATIVE",
+        _ => "CONFIRMED",
+    };
+    lines.push(format!("STATUS:{}", status));
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+fn summary(game: &Game) -> String {
+    if game.is_finished() {
+        format!(
+            "{} {} @ {} {}",
+            game.teams.away.team.display_name(),
+            game.teams.away.score,
+            game.teams.home.team.display_name(),
+            game.teams.home.score
+        )
+    } else {
+        game.describe_upcoming_teams()
+    }
+}
+
+fn timestamp(date_time: DateTime<Utc>) -> String {
+    date_time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// RFC 5545 content lines are limited to 75 octets; longer lines continue
+/// on the next line after a single space.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+
+    fn games() -> Vec<Game> {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        schedule.dates[0].games.clone()
+    }
+
+    #[test]
+    fn test_favorites_calendar() {
+        let mut previews = HashMap::new();
+        previews.insert(2022020275, "NBCSCA, SNW; ESPN+".to_string());
+        let now = DateTime::parse_from_rfc3339("2022-11-20T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ics = calendar(&games(), &previews, &GameFilter::favorites(), now);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("UID:2022020275@games-today\r\n"));
+        assert!(ics.contains("DTSTART:20221121T000000Z\r\n"));
+        assert!(ics.contains("DTEND:20221121T023000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Edmonton Oilers 3 @ San Jose Sharks 4\r\n"));
+        assert!(ics.contains("LOCATION:SAP Center at San Jose\\, San Jose\r\n"));
+        assert!(ics.contains("DESCRIPTION:Broadcasters: NBCSCA\\, SNW\\; ESPN+\r\n"));
+    }
+
+    #[test]
+    fn test_tbd_and_postponed() {
+        let now = Utc::now();
+        let ics = calendar(&games(), &HashMap::new(), &GameFilter::All, now);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 7);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20221120\r\n"));
+        assert!(ics.contains("STATUS:TENTATIVE\r\n"));
+        assert!(ics.contains("STATUS:CANCELLED\r\n"));
+
+        // Midnight Eastern on daylight time is still the scheduled date.
+        let mut october = games().into_iter().find(|game| game.is_tbd()).unwrap();
+        october.game_date = "2022-10-20T04:00:00Z".parse().unwrap();
+        let ics = calendar(&[october], &HashMap::new(), &GameFilter::All, now);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20221020\r\n"));
+    }
+
+    #[test]
+    fn test_historical_team_names() {
+        let mut game = games().into_iter().find(|game| game.is_finished()).unwrap();
+        game.teams.away.team.name = String::new();
+        assert_eq!(summary(&game), "Edmonton Oilers 3 @ San Jose Sharks 4");
+    }
+
+    #[test]
+    fn test_fold() {
+        let long = "D".repeat(200);
+        let folded = fold(&long);
+        assert!(folded
+            .split("\r\n")
+            .all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+}
@@ -1,15 +1,42 @@
-use chrono::{DateTime, FixedOffset, Timelike, Utc};
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     iter::FromIterator,
 };
-
+use locale::{Label, Locale};
+use summary::{GameSummary, Layout, SummaryFormatter, TextFormatter};
+
+pub mod api;
+pub mod boxscore;
+pub mod cache;
+pub mod changes;
+pub mod drift;
+pub mod franchise;
+pub mod gordle;
+pub mod head_to_head;
+pub mod history;
+pub mod ics;
+pub mod league;
+pub mod live;
+pub mod locale;
+pub mod normalized;
+pub mod notifications;
+#[cfg(feature = "web_app")]
 pub mod pages;
+#[cfg(all(not(target_arch = "wasm32"), feature = "native_http"))]
+pub mod proxy;
+pub mod ratings;
+pub mod season;
+pub mod site;
+pub mod summary;
+pub mod travel;
+#[cfg(all(not(target_arch = "wasm32"), feature = "native_http"))]
+pub mod upstream;
+pub mod win_probability;
 
 pub mod teams {
-    use crate::Team;
     use deunicode::deunicode;
     use inflector::Inflector;
     use serde::{Deserialize, Serialize};
@@ -47,9 +74,12 @@ pub mod teams {
             (29, "Jackets"),
             (30, "Wild"),
             (52, "Jets"),
-            (54, "Coyotes"),
-            (55, "Knights"),
-            (56, "Kraken")
+            // These three were once listed as 54, 55 and 56, which called
+            // Vegas the Coyotes and Seattle the Knights; the ids are the
+            // ones in teams.json.
+            (53, "Coyotes"),
+            (54, "Knights"),
+            (55, "Kraken")
         ].iter().cloned().collect()
     });
 
@@ -120,20 +150,78 @@ pub mod teams {
             VEGAS_GOLDEN_KNIGHTS_ID => "Vegas Golden Knights",
             WASHINGTON_CAPITALS_ID => "Washington Capitals",
             WINNIPEG_JETS_ID => "Winnipeg Jets",
-            _ => "",
+            _ => crate::franchise::team_name(team_id).unwrap_or(""),
         }
     }
 
+    /// Teams whose games get highlighted and are included when exporting
+    /// favorites.
+    pub const FAVORITE_TEAM_IDS: &[usize] = &[SAN_JOSE_SHARKS_ID, EDMONTON_OILERS_ID];
+
     const TEAMS_TEXT: &str = include_str!("../data/teams.json");
 
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct TimeZone {
+        pub id: String,
+        pub offset: i32,
+        pub tz: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Venue {
+        pub name: String,
+        pub city: String,
+        pub time_zone: TimeZone,
+    }
+
+    impl Venue {
+        pub fn location(&self) -> String {
+            format!("{}, {}", self.name, self.city)
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct TeamInfo {
+        pub id: usize,
+        pub name: String,
+        pub abbreviation: String,
+        pub team_name: String,
+        pub location_name: String,
+        pub venue: Venue,
+        pub franchise_id: usize,
+        pub active: bool,
+    }
+
     #[derive(Serialize, Deserialize, Debug, Clone)]
     #[serde(rename_all = "camelCase")]
     struct AllTeams {
-        teams: Vec<Team>,
+        teams: Vec<TeamInfo>,
+    }
+
+    pub static TEAM_REGISTRY: Lazy<HashMap<usize, TeamInfo>> = Lazy::new(|| {
+        let all_teams: AllTeams = serde_json::from_str(TEAMS_TEXT).expect("from_str");
+        all_teams
+            .teams
+            .into_iter()
+            .map(|team| (team.id, team))
+            .collect()
+    });
+
+    pub fn team_info(team_id: usize) -> Option<&'static TeamInfo> {
+        TEAM_REGISTRY.get(&team_id)
+    }
+
+    pub fn team_by_abbreviation(abbreviation: &str) -> Option<&'static TeamInfo> {
+        TEAM_REGISTRY
+            .values()
+            .find(|team| team.abbreviation.eq_ignore_ascii_case(abbreviation))
     }
 
     pub fn get_teams() {
-        let all_teams: AllTeams = serde_json::from_str(&TEAMS_TEXT).expect("from_str");
+        let all_teams: AllTeams = serde_json::from_str(TEAMS_TEXT).expect("from_str");
         for team in all_teams.teams {
             println!(
                 "{}_ID => \"{}\",",
@@ -148,9 +236,22 @@ pub mod teams {
 #[serde(rename_all = "camelCase")]
 pub struct Team {
     pub id: usize,
+    #[serde(default)]
     pub name: String,
 }
 
+impl Team {
+    /// The name from the API, or the historical name for the id when the
+    /// response leaves it out.
+    pub fn display_name(&self) -> &str {
+        if self.name.is_empty() {
+            franchise::team_name(self.id).unwrap_or_default()
+        } else {
+            &self.name
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TeamAtGame {
@@ -192,6 +293,115 @@ pub struct IntermissionInfo {
     in_intermission: bool,
 }
 
+/// Team totals from the boxscore. The API sends percentages as strings
+/// and power-play counts as floats.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TeamSkaterStats {
+    pub goals: usize,
+    pub pim: usize,
+    pub shots: usize,
+    pub power_play_percentage: String,
+    pub power_play_goals: f64,
+    pub power_play_opportunities: f64,
+    pub face_off_win_percentage: String,
+    pub blocked: usize,
+    pub takeaways: usize,
+    pub giveaways: usize,
+    pub hits: usize,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStats {
+    pub team_skater_stats: TeamSkaterStats,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SkaterStats {
+    pub time_on_ice: String,
+    pub goals: usize,
+    pub assists: usize,
+    pub shots: usize,
+    pub hits: usize,
+    pub blocked: usize,
+    pub penalty_minutes: usize,
+    pub face_off_wins: usize,
+    pub faceoff_taken: usize,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GoalieStats {
+    pub time_on_ice: String,
+    pub shots: usize,
+    pub saves: usize,
+    pub decision: String,
+    pub save_percentage: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    pub skater_stats: Option<SkaterStats>,
+    pub goalie_stats: Option<GoalieStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxscorePlayer {
+    pub person: live::Person,
+    #[serde(default)]
+    pub jersey_number: String,
+    #[serde(default)]
+    pub stats: PlayerStats,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxscoreTeam {
+    pub team: live::FeedTeam,
+    pub team_stats: TeamStats,
+    /// Keyed by `ID` followed by the player id.
+    #[serde(default)]
+    pub players: HashMap<String, BoxscorePlayer>,
+    #[serde(default)]
+    pub goalies: Vec<usize>,
+    #[serde(default)]
+    pub skaters: Vec<usize>,
+}
+
+impl BoxscoreTeam {
+    pub fn player(&self, id: usize) -> Option<&BoxscorePlayer> {
+        self.players.get(&format!("ID{}", id))
+    }
+
+    /// Goalies who played, in the order the API lists them.
+    pub fn goalies_played(&self) -> Vec<(&BoxscorePlayer, &GoalieStats)> {
+        self.goalies
+            .iter()
+            .filter_map(|id| self.player(*id))
+            .filter_map(|player| Some((player, player.stats.goalie_stats.as_ref()?)))
+            .filter(|(_, stats)| !stats.time_on_ice.is_empty() && stats.time_on_ice != "0:00")
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxscoreTeams {
+    pub away: BoxscoreTeam,
+    pub home: BoxscoreTeam,
+}
+
+/// `api/v1/game/{game_pk}/boxscore`, also embedded in the live feed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Boxscore {
+    pub teams: BoxscoreTeams,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ContentLink {
@@ -204,12 +414,23 @@ fn parse_preview_string(preview: &str) -> Option<String> {
     Some(captures.get(1)?.as_str().to_string())
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum GameState {
+    Preview,
+    Live,
+    Final,
+    Postponed,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Game {
     pub game_pk: usize,
     pub game_date: DateTime<Utc>,
     pub game_type: String,
+    #[serde(default)]
+    pub season: String,
     pub content: ContentLink,
     pub teams: Teams,
     pub status: Status,
@@ -218,85 +439,48 @@ pub struct Game {
 }
 
 impl Game {
+    pub fn summary(&self, offset: f64) -> GameSummary {
+        GameSummary::new(self, offset)
+    }
+
     pub fn describe(&self, offset: f64) -> String {
-        if self.is_finished() {
-            format!(
-                "{} @ {}",
-                self.teams.away.team.name, self.teams.home.team.name,
-            )
-        } else if self.is_live() {
-            if self.linescore.intermission_info.in_intermission {
-                format!(
-                    "{} @ {} {} {}:{:02} INT",
-                    self.teams.away.team.name,
-                    self.teams.home.team.name,
-                    self.linescore.current_period_ordinal,
-                    self.linescore.intermission_info.intermission_time_remaining / 60,
-                    self.linescore.intermission_info.intermission_time_remaining % 60
-                )
-            } else {
-                format!(
-                    "{} @ {} {} {}",
-                    self.teams.away.team.name,
-                    self.teams.home.team.name,
-                    self.linescore.current_period_ordinal,
-                    self.linescore.current_period_time_remaining
-                )
-            }
-        } else {
-            if self.is_tbd() {
-                format!(
-                    "{} @ {}",
-                    self.teams.away.team.name, self.teams.home.team.name,
-                )
-            } else {
-                let tz = FixedOffset::west_opt(offset as i32).unwrap();
-                let t = self.game_date.with_timezone(&tz).time();
-                let (pm, h) = t.hour12();
-                let pm_str = if pm { "PM" } else { "AM" };
-                format!(
-                    "{: >2}:{:02} {} {} @ {}",
-                    h,
-                    t.minute(),
-                    pm_str,
-                    self.teams.away.team.name,
-                    self.teams.home.team.name,
-                )
-            }
-        }
+        self.describe_localized(offset, Locale::English)
     }
 
-    pub fn describe_with_preview(&self, offset: f64, previews: &HashMap<usize, String>) -> String {
+    pub fn describe_localized(&self, offset: f64, locale: Locale) -> String {
+        TextFormatter::default()
+            .localized(locale)
+            .format(&self.summary(offset))
+    }
+
+    pub fn describe_with_preview(
+        &self,
+        offset: f64,
+        previews: &HashMap<usize, String>,
+        locale: Locale,
+    ) -> String {
         let preview = previews.get(&self.game_pk).cloned().unwrap_or_default();
-        if preview.len() > 0 {
-            format!("{} ({})", self.describe(offset), preview)
-        } else {
-            self.describe(offset)
+        TextFormatter::default()
+            .localized(locale)
+            .format(&self.summary(offset).with_preview(&preview))
+    }
+
+    /// `describe_localized`, followed by the season series so far when
+    /// the teams have already met among `season_games`.
+    pub fn describe_with_series(&self, offset: f64, season_games: &[Game], locale: Locale) -> String {
+        let description = self.describe_localized(offset, locale);
+        match head_to_head::SeasonSeries::before(self, season_games) {
+            Some(series) => format!("{} ({})", description, series.describe_in(locale)),
+            None => description,
         }
     }
 
     pub fn describe_upcoming(&self, offset: f64) -> String {
-        let tz = FixedOffset::west_opt(offset as i32).unwrap();
-        let d = self.game_date.with_timezone(&tz);
-        let t = d.time();
-        let (pm, h) = t.hour12();
-        let pm_str = if pm { "PM" } else { "AM" };
-        format!(
-            "{} {: >2}:{:02} {} {} @ {}",
-            d.format("%v"),
-            h,
-            t.minute(),
-            pm_str,
-            self.teams.away.team.name,
-            self.teams.home.team.name,
-        )
+        TextFormatter::new(Layout::Upcoming).format(&self.summary(offset))
     }
 
     pub fn describe_upcoming_teams(&self) -> String {
-        format!(
-            "{} @ {}",
-            self.teams.away.team.name, self.teams.home.team.name,
-        )
+        TextFormatter::new(Layout::TeamsOnly).format(&self.summary(0.0))
     }
 
     pub fn class(&self) -> String {
@@ -321,6 +505,11 @@ impl Game {
         self.status.abstract_game_state == "Final"
     }
 
+    /// The API's status, e.g. "Scheduled", "In Progress" or "Postponed".
+    pub fn detailed_state(&self) -> &str {
+        &self.status.detailed_state
+    }
+
     pub fn is_tbd(&self) -> bool {
         self.status.detailed_state == "Scheduled (Time TBD)"
     }
@@ -329,6 +518,29 @@ impl Game {
         self.game_type == "R"
     }
 
+    pub fn is_playoffs(&self) -> bool {
+        self.game_type == "P"
+    }
+
+    /// A label for anything other than a regular season game.
+    pub fn game_type_label(&self) -> Option<Label> {
+        match self.game_type.as_str() {
+            "R" => None,
+            "PR" => Some(Label::Preseason),
+            "P" => Some(Label::Playoffs),
+            "A" => Some(Label::AllStar),
+            "WA" => Some(Label::WomensAllStar),
+            "O" => Some(Label::Olympics),
+            game_type if game_type.starts_with("WC") => Some(Label::WorldCup),
+            _ => Some(Label::Exhibition),
+        }
+    }
+
+    /// Finished after regulation, in overtime or a shootout.
+    pub fn went_to_overtime(&self) -> bool {
+        self.is_finished() && self.linescore.current_period > 3
+    }
+
     pub fn is_postponed(&self) -> bool {
         self.status.detailed_state == "Postponed"
     }
@@ -341,6 +553,18 @@ impl Game {
         self.status.abstract_game_state == "Live"
     }
 
+    pub fn state(&self) -> GameState {
+        if self.is_finished() {
+            GameState::Final
+        } else if self.is_live() {
+            GameState::Live
+        } else if self.is_postponed() {
+            GameState::Postponed
+        } else {
+            GameState::Preview
+        }
+    }
+
     pub fn has_competitor(&self, competitor: usize) -> bool {
         self.teams.away.team.id == competitor || self.teams.home.team.id == competitor
     }
@@ -376,6 +600,40 @@ impl Game {
     }
 }
 
+/// A day's games split into the sections shown on the page.
+#[derive(Debug, Clone, Default)]
+pub struct GameGroups<'a> {
+    pub live: Vec<&'a Game>,
+    pub preview: Vec<&'a Game>,
+    pub finished: Vec<&'a Game>,
+    pub postponed: Vec<&'a Game>,
+}
+
+impl<'a> GameGroups<'a> {
+    pub fn new(games: &'a [Game]) -> Self {
+        Self {
+            live: games.iter().filter(|game| game.is_live()).collect(),
+            preview: games.iter().filter(|game| game.is_preview()).collect(),
+            finished: games.iter().filter(|game| game.is_finished()).collect(),
+            postponed: games.iter().filter(|game| game.is_postponed()).collect(),
+        }
+    }
+
+    /// Section titles and their games, in page order.
+    pub fn sections(&self) -> [(&'static str, &[&'a Game]); 4] {
+        self.sections_in(Locale::English)
+    }
+
+    pub fn sections_in(&self, locale: Locale) -> [(&'static str, &[&'a Game]); 4] {
+        [
+            (locale.text(Label::Live), &self.live),
+            (locale.text(Label::Upcoming), &self.preview),
+            (locale.text(Label::Finished), &self.finished),
+            (locale.text(Label::Postponed), &self.postponed),
+        ]
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PreviewItem {
@@ -404,7 +662,7 @@ pub struct Content {
 
 impl Content {
     pub fn preview_string(&self) -> Option<String> {
-        parse_preview_string(&self.editorial.preview.items.get(0)?.preview)
+        parse_preview_string(&self.editorial.preview.items.first()?.preview)
     }
 }
 
@@ -415,7 +673,7 @@ pub struct Date {
     pub games: Vec<Game>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Schedule {
     pub total_games: usize,
@@ -436,15 +694,6 @@ pub struct NextGameSchedule {
     pub dates: Vec<GameDate>,
 }
 
-impl Default for Schedule {
-    fn default() -> Self {
-        Self {
-            total_games: 0,
-            dates: vec![],
-        }
-    }
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Franchise {
@@ -471,6 +720,24 @@ pub struct PlayerRecordData {
     pub data: Vec<PlayerRecord>,
 }
 
+/// Hand-picked, not generated. `gordle-words --length 4 --rust` prints a
+/// replacement in this format from the player records. "O'Ree" is four
+/// letters once the apostrophe is dropped, so it lives here rather than
+/// with the five-letter names.
+pub const FOUR_LETTER_LAST_NAMES: &[&str] = &[
+    "Adam", "Bass", "Bell", "Berg", "Boll", "Boyd", "Bure", "Ceci", "Cole", "Cook",
+    "Copp", "Cote", "Dach", "Dano", "Doan", "Domi", "Dowd", "Dube", "Duff", "Erne",
+    "Fast", "Fata", "Fehr", "Fuhr", "Gare", "Gill", "Haas", "Hagg", "Hall", "Hart",
+    "Holl", "Howe", "Huet", "Hull", "Hunt", "Jagr", "Jost", "Kane", "Kase", "Keon",
+    "Kerr", "King", "Krug", "Kuba", "Lack", "Ladd", "Lang", "Lowe", "Maki", "Mara",
+    "Mete", "Moen", "Moog", "Moss", "Muni", "Nagy", "Nash", "Neal", "Neil", "Ness",
+    "ORee", "Otto", "Pang", "Park", "Peca", "Rask", "Ross", "Ruff", "Rupp", "Rust",
+    "Ryan", "Saad", "Salo", "Shaw", "Smid", "Snow", "Tuch", "Voss", "Ward", "Weal",
+    "Witt", "Wood", "York", "Zuke",
+];
+
+/// The original Gordle list, not generated. `gordle-words --length 5
+/// --rust` prints a replacement in this format from the player records.
 pub const FIVE_LETTER_LAST_NAMES: &[&str] = &[
     "Aalto", "Aberg", "Acomb", "Acton", "Adams", "Agnew", "Ahcan", "Ahern", "Ahlin", "Ahola",
     "Allan", "Allen", "Alley", "Allum", "Alves", "Armia", "Asham", "Ashby", "Aubin", "Aubry",
@@ -527,7 +794,7 @@ pub const FIVE_LETTER_LAST_NAMES: &[&str] = &[
     "Miner", "Minor", "Modin", "Modry", "Moger", "Moher", "Mohns", "Molin", "Moore", "Moran",
     "Morin", "Moser", "Motin", "Motte", "Moxey", "Mozik", "Munro", "Musil", "Myers", "Myles",
     "Nanne", "Necas", "Neely", "Nevin", "Niemi", "Nieto", "Nigro", "Nilan", "Noble", "Nolan",
-    "Nolet", "Noris", "Nosek", "Novak", "Nowak", "Nurse", "Nyrop", "O'Ree", "Oates", "Oberg",
+    "Nolet", "Noris", "Nosek", "Novak", "Nowak", "Nurse", "Nyrop", "Oates", "Oberg",
     "Obsut", "Oduya", "Olesz", "Oliwa", "Olsen", "Olson", "Olver", "Omark", "Orban", "Orlov",
     "Orpik", "Osala", "Oshie", "Palat", "Panik", "Pardy", "Parks", "Parro", "Parse", "Pasek",
     "Pasin", "Patey", "Payer", "Payne", "Peake", "Pedan", "Peeke", "Pelyk", "Percy", "Perry",
@@ -558,6 +825,27 @@ pub const FIVE_LETTER_LAST_NAMES: &[&str] = &[
     "Zubov", "Zykov",
 ];
 
+/// Hand-picked, not generated. `gordle-words --length 6 --rust` prints a
+/// replacement in this format from the player records.
+pub const SIX_LETTER_LAST_NAMES: &[&str] = &[
+    "Alzner", "Arnott", "Backes", "Barrie", "Barron", "Barzal", "Beagle", "Bonino", "Borgen", "Brodie",
+    "Carter", "Chytil", "Coffey", "Comrie", "Connor", "Copley", "Crosby", "Dahlin", "DeMelo", "Dillon",
+    "Dionne", "Donato", "Drouin", "Dryden", "Duguay", "Dvorak", "Eberle", "Ehlers", "Ekblad", "Ekholm",
+    "Eklund", "Fleury", "Frolov", "Gagner", "Gainey", "Gionta", "Girard", "Graves", "Greene", "Guerin",
+    "Hannan", "Harvey", "Hedman", "Heinen", "Hejduk", "Hemsky", "Hinote", "Holtby", "Horton", "Hughes",
+    "Janney", "Jensen", "Joseph", "Juneau", "Kaleta", "Keller", "Kempny", "Kessel", "Knuble", "Kostin",
+    "Krejci", "Kruger", "Kubina", "Kunitz", "Kuraly", "Labanc", "Lauzon", "Leetch", "Letang", "Little",
+    "Madden", "Malkin", "Manson", "Mantha", "Marino", "Markov", "Marner", "Maroon", "McAvoy", "McCabe",
+    "McLeod", "Mercer", "Milano", "Miller", "Moreau", "Mrazek", "Murphy", "Murray", "Muzzin", "Nelson",
+    "Ohlund", "Pageau", "Paille", "Parise", "Peluso", "Penner", "Pisani", "Plante", "Potvin", "Protas",
+    "Recchi", "Reimer", "Rielly", "Sandin", "Savard", "Schenn", "Seguin", "Sheary", "Simmer", "Slater",
+    "Spezza", "Staios", "Stajan", "Stepan", "Strome", "Stuart", "Subban", "Sundin", "Sutter", "Suzuki",
+    "Talbot", "Taylor", "Thomas", "Tootoo", "Torres", "Trouba", "Tucker", "Vlasic", "Voynov", "Wagner",
+    "Walker", "Weight", "Wilson", "Winnik", "Wolski", "Wright", "Zadina", "Zboril", "Zubrus",
+];
+
+/// Letter sets can't say how many times a letter appears; see
+/// `gordle::GordleState` for filtering with exact Wordle feedback.
 pub fn gordle_guesses(
     valid_letters: String,
     bad_letters: String,
@@ -569,13 +857,7 @@ pub fn gordle_guesses(
         .map(|name| HashSet::from_iter(name.to_lowercase().chars()))
         .collect();
 
-    let valid_set = HashSet::from_iter(valid_letters.to_lowercase().chars().filter_map(|c| {
-        if c != '.' {
-            Some(c)
-        } else {
-            None
-        }
-    }));
+    let valid_set = HashSet::from_iter(valid_letters.to_lowercase().chars().filter(|c| *c != '.'));
     let valid_vec: Vec<_> = valid_letters
         .to_lowercase()
         .chars()
@@ -589,21 +871,17 @@ pub fn gordle_guesses(
         .collect();
     'outer: for (name_set, name) in name_sets.iter().zip(FIVE_LETTER_LAST_NAMES.iter()) {
         for (placed, test) in placed.iter().zip(name.to_lowercase().chars()) {
-            if placed.is_some() {
-                if Some(test) != *placed {
-                    continue 'outer;
-                }
+            if placed.is_some() && Some(test) != *placed {
+                continue 'outer;
             }
         }
         let bad_intersection: HashSet<_> = bad_set.intersection(name_set).collect();
-        if bad_intersection.len() == 0 {
+        if bad_intersection.is_empty() {
             let intersection: HashSet<_> = valid_set.intersection(name_set).collect();
             if intersection.len() == valid_set.len() {
                 for (valid, test) in valid_vec.iter().zip(name.to_lowercase().chars()) {
-                    if valid.is_some() {
-                        if Some(test) == *valid {
-                            continue 'outer;
-                        }
+                    if valid.is_some() && Some(test) == *valid {
+                        continue 'outer;
                     }
                 }
                 names.push(name.to_string());
@@ -625,4 +903,17 @@ mod test {
 
         assert_eq!("NBCSCA, BSDET, ESPN+, SN NOW", &parsed);
     }
+
+    #[test]
+    fn test_team_nicknames() {
+        for (team_id, nickname) in teams::TEAM_NICKNAMES.iter() {
+            let name = &teams::team_info(*team_id).unwrap().name;
+            assert!(
+                name.to_lowercase().ends_with(&nickname.to_lowercase()),
+                "{} is not {}",
+                nickname,
+                name
+            );
+        }
+    }
 }
@@ -0,0 +1,405 @@
+use crate::{Boxscore, IntermissionInfo, Status};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub id: usize,
+    pub full_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedTeam {
+    pub id: usize,
+    pub name: String,
+    #[serde(default)]
+    pub abbreviation: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedTeams {
+    pub away: FeedTeam,
+    pub home: FeedTeam,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameData {
+    pub status: Status,
+    pub teams: FeedTeams,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Strength {
+    pub code: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayResult {
+    pub event: String,
+    pub event_type_id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub secondary_type: Option<String>,
+    #[serde(default)]
+    pub penalty_severity: Option<String>,
+    #[serde(default)]
+    pub penalty_minutes: Option<usize>,
+    #[serde(default)]
+    pub strength: Option<Strength>,
+    #[serde(default)]
+    pub empty_net: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalsAtPlay {
+    pub away: usize,
+    pub home: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct About {
+    pub period: usize,
+    pub ordinal_num: String,
+    pub period_time: String,
+    pub period_time_remaining: String,
+    #[serde(default)]
+    pub goals: GoalsAtPlay,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayPlayer {
+    pub player: Person,
+    pub player_type: String,
+    #[serde(default)]
+    pub season_total: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayTeam {
+    pub id: usize,
+    pub name: String,
+    #[serde(default)]
+    pub tri_code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Play {
+    pub result: PlayResult,
+    pub about: About,
+    #[serde(default)]
+    pub players: Vec<PlayPlayer>,
+    #[serde(default)]
+    pub team: Option<PlayTeam>,
+}
+
+impl Play {
+    fn players_of_type<'a>(&'a self, player_type: &'a str) -> impl Iterator<Item = &'a PlayPlayer> {
+        self.players
+            .iter()
+            .filter(move |player| player.player_type == player_type)
+    }
+
+    pub fn scorer(&self) -> Option<&PlayPlayer> {
+        self.players_of_type("Scorer").next()
+    }
+
+    pub fn assists(&self) -> Vec<&PlayPlayer> {
+        self.players_of_type("Assist").collect()
+    }
+
+    pub fn penalty_on(&self) -> Option<&PlayPlayer> {
+        self.players_of_type("PenaltyOn").next()
+    }
+
+    /// "Erik Karlsson (11) from Tomas Hertl (12)", or "unassisted".
+    pub fn goal_summary(&self) -> String {
+        let name = |player: &PlayPlayer| match player.season_total {
+            Some(total) => format!("{} ({})", player.player.full_name, total),
+            None => player.player.full_name.clone(),
+        };
+        let scorer = self.scorer().map(name).unwrap_or_default();
+        let assists: Vec<String> = self.assists().into_iter().map(name).collect();
+        let strength = match self.result.strength.as_ref().map(|s| s.code.as_str()) {
+            Some("PPG") => " PPG",
+            Some("SHG") => " SHG",
+            _ => "",
+        };
+        if assists.is_empty() {
+            format!("{}{}, unassisted", scorer, strength)
+        } else {
+            format!("{}{} from {}", scorer, strength, assists.join(", "))
+        }
+    }
+
+    /// "Mario Ferraro 2:00 Tripping"
+    pub fn penalty_summary(&self) -> String {
+        format!(
+            "{} {}:00 {}",
+            self.penalty_on()
+                .map(|player| player.player.full_name.as_str())
+                .unwrap_or("Bench"),
+            self.result.penalty_minutes.unwrap_or_default(),
+            self.result.secondary_type.clone().unwrap_or_default()
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Plays {
+    pub all_plays: Vec<Play>,
+    #[serde(default)]
+    pub scoring_plays: Vec<usize>,
+    #[serde(default)]
+    pub penalty_plays: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodTeam {
+    #[serde(default)]
+    pub goals: usize,
+    #[serde(default)]
+    pub shots_on_goal: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Period {
+    pub num: usize,
+    pub ordinal_num: String,
+    pub home: PeriodTeam,
+    pub away: PeriodTeam,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LinescoreTeam {
+    #[serde(default)]
+    pub goals: usize,
+    #[serde(default)]
+    pub shots_on_goal: usize,
+    #[serde(default)]
+    pub goalie_pulled: bool,
+    #[serde(default)]
+    pub num_skaters: usize,
+    #[serde(default)]
+    pub power_play: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LinescoreTeams {
+    pub home: LinescoreTeam,
+    pub away: LinescoreTeam,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerPlayInfo {
+    pub situation_time_remaining: usize,
+    pub in_situation: bool,
+}
+
+/// The linescore as it appears in the live feed, which has more detail
+/// than the one expanded into the schedule.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveLinescore {
+    #[serde(default)]
+    pub current_period: usize,
+    #[serde(default)]
+    pub current_period_ordinal: String,
+    #[serde(default)]
+    pub current_period_time_remaining: String,
+    #[serde(default)]
+    pub periods: Vec<Period>,
+    #[serde(default)]
+    pub teams: LinescoreTeams,
+    #[serde(default)]
+    pub power_play_strength: String,
+    #[serde(default)]
+    pub power_play_info: Option<PowerPlayInfo>,
+    #[serde(default)]
+    pub intermission_info: IntermissionInfo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Decisions {
+    pub winner: Option<Person>,
+    pub loser: Option<Person>,
+    pub first_star: Option<Person>,
+    pub second_star: Option<Person>,
+    pub third_star: Option<Person>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveData {
+    pub plays: Plays,
+    pub linescore: LiveLinescore,
+    #[serde(default)]
+    pub decisions: Decisions,
+    #[serde(default)]
+    pub boxscore: Option<Boxscore>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFeed {
+    pub game_pk: usize,
+    pub game_data: GameData,
+    pub live_data: LiveData,
+}
+
+impl LiveFeed {
+    pub fn is_live(&self) -> bool {
+        self.game_data.status.abstract_game_state == "Live"
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.game_data.status.abstract_game_state == "Final"
+    }
+
+    fn plays_at<'a>(&'a self, indexes: &'a [usize]) -> impl Iterator<Item = &'a Play> {
+        indexes
+            .iter()
+            .filter_map(move |index| self.live_data.plays.all_plays.get(*index))
+    }
+
+    pub fn scoring_plays(&self) -> Vec<&Play> {
+        self.plays_at(&self.live_data.plays.scoring_plays).collect()
+    }
+
+    pub fn penalty_plays(&self) -> Vec<&Play> {
+        self.plays_at(&self.live_data.plays.penalty_plays).collect()
+    }
+
+    /// Plays grouped by period number, in the order the periods were played.
+    pub fn by_period<'a>(&'a self, plays: &[&'a Play]) -> Vec<(String, Vec<&'a Play>)> {
+        let mut periods: Vec<(usize, String, Vec<&Play>)> = vec![];
+        for play in plays {
+            match periods
+                .iter_mut()
+                .find(|(num, _, _)| *num == play.about.period)
+            {
+                Some((_, _, period_plays)) => period_plays.push(play),
+                None => periods.push((
+                    play.about.period,
+                    play.about.ordinal_num.clone(),
+                    vec![play],
+                )),
+            }
+        }
+        periods.sort_by_key(|(num, _, _)| *num);
+        periods
+            .into_iter()
+            .map(|(_, ordinal, plays)| (ordinal, plays))
+            .collect()
+    }
+
+    /// "SJS power play 1:23 (5-on-4)" while a team has the advantage.
+    pub fn power_play_state(&self) -> Option<String> {
+        let info = self.live_data.linescore.power_play_info.as_ref()?;
+        if !info.in_situation || !self.is_live() {
+            return None;
+        }
+        let teams = &self.live_data.linescore.teams;
+        let (team, skaters, opponent_skaters) = if teams.home.power_play {
+            (
+                &self.game_data.teams.home,
+                teams.home.num_skaters,
+                teams.away.num_skaters,
+            )
+        } else if teams.away.power_play {
+            (
+                &self.game_data.teams.away,
+                teams.away.num_skaters,
+                teams.home.num_skaters,
+            )
+        } else {
+            return None;
+        };
+        Some(format!(
+            "{} power play {}:{:02} ({}-on-{})",
+            team.abbreviation,
+            info.situation_time_remaining / 60,
+            info.situation_time_remaining % 60,
+            skaters,
+            opponent_skaters
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn feed() -> LiveFeed {
+        serde_json::from_str(include_str!(
+            "../data/fixtures/game-2022020275-feed-live.json"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scoring_plays() {
+        let feed = feed();
+        let goals = feed.scoring_plays();
+        assert_eq!(goals.len(), 7);
+        assert_eq!(
+            goals[0].goal_summary(),
+            "Connor McDavid (18) from Leon Draisaitl (20), Evan Bouchard (9)"
+        );
+        assert_eq!(
+            goals[1].goal_summary(),
+            "Leon Draisaitl (12) PPG from Connor McDavid (25)"
+        );
+        assert_eq!(goals[4].goal_summary(), "Zach Hyman (7), unassisted");
+
+        let periods = feed.by_period(&goals);
+        let ordinals: Vec<_> = periods
+            .iter()
+            .map(|(ordinal, _)| ordinal.as_str())
+            .collect();
+        assert_eq!(ordinals, ["1st", "2nd", "3rd", "OT"]);
+        assert_eq!(periods[3].1[0].team.as_ref().unwrap().tri_code, "SJS");
+    }
+
+    #[test]
+    fn test_penalties_and_power_play() {
+        let mut feed = feed();
+        let penalties = feed.penalty_plays();
+        assert_eq!(penalties.len(), 2);
+        assert_eq!(
+            penalties[0].penalty_summary(),
+            "Mario Ferraro 2:00 Tripping"
+        );
+        assert_eq!(feed.power_play_state(), None);
+
+        feed.game_data.status.abstract_game_state = "Live".to_string();
+        feed.live_data.linescore.power_play_info = Some(PowerPlayInfo {
+            situation_time_remaining: 83,
+            in_situation: true,
+        });
+        feed.live_data.linescore.teams.away.power_play = true;
+        feed.live_data.linescore.teams.home.num_skaters = 4;
+        assert_eq!(
+            feed.power_play_state().unwrap(),
+            "EDM power play 1:23 (5-on-4)"
+        );
+    }
+}
@@ -0,0 +1,539 @@
+use anyhow::{bail, Error};
+use chrono::{Datelike, NaiveDate, Timelike};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+}
+
+/// Every fixed piece of text the page and the game descriptions show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Label {
+    Live,
+    Upcoming,
+    Finished,
+    Postponed,
+    Loading,
+    Update,
+    AddToCalendar,
+    /// After the intermission clock, e.g. "1st 12:34 INT".
+    Intermission,
+    Final,
+    Overtime,
+    TimeTbd,
+    NoGames,
+    Offseason,
+    NotifyFavorites,
+    GameStart,
+    Goals,
+    Intermissions,
+    FinalScore,
+    GoalHorn,
+    Language,
+    Leagues,
+    AllLeagues,
+    AllGames,
+    RatingsUnavailable,
+    GameUnavailable,
+    PowerRatings,
+    Team,
+    Rating,
+    /// Under a heading with nothing to list.
+    NoPlays,
+    BoxScore,
+    Goalies,
+    ThreeStars,
+    Scoring,
+    Penalties,
+    GordleHelper,
+    GordleInstructions,
+    Letters,
+    Remove,
+    Guess,
+    Add,
+    Reset,
+    TryNext,
+    /// Marks a suggestion that could itself be the answer.
+    Possible,
+    DailyGordle,
+    /// Before the answer once it's guessed.
+    GotIt,
+    /// Before the answer once the guesses run out.
+    AnswerWas,
+    Copied,
+    CopyResult,
+    Statistics,
+    Name,
+    /// Limits the calendar download to the favorite teams.
+    FavoritesOnly,
+    /// Explains the win chance badge on upcoming games.
+    WinChance,
+    /// Before the contact address at the bottom of every page.
+    QuestionsComments,
+    NotFound,
+    TodaysGames,
+    ThisDayInHistory,
+    Preseason,
+    Playoffs,
+    AllStar,
+    WomensAllStar,
+    Olympics,
+    WorldCup,
+    Exhibition,
+    /// Badges on past results worth a second look.
+    Favorite,
+    WentToOvertime,
+    Blowout,
+    HighScoring,
+}
+
+const FRENCH_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+const FRENCH_SHORT_MONTHS: [&str; 12] = [
+    "janv.", "févr.", "mars", "avr.", "mai", "juin", "juill.", "août", "sept.", "oct.", "nov.",
+    "déc.",
+];
+
+const FRENCH_WEEKDAYS: [&str; 7] = [
+    "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+];
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::French];
+
+    /// Picks a locale from a language tag like `fr-CA`, as browsers
+    /// report it, falling back to English.
+    pub fn from_language_tag(tag: &str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        if language.eq_ignore_ascii_case("fr") {
+            Locale::French
+        } else {
+            Locale::English
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::French => "fr",
+        }
+    }
+
+    /// The locale's name for itself, for a language picker.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::French => "Français",
+        }
+    }
+
+    pub fn text(&self, label: Label) -> &'static str {
+        use Label::*;
+        match self {
+            Locale::English => match label {
+                Live => "Live",
+                Upcoming => "Upcoming",
+                Finished => "Finished",
+                Postponed => "Postponed",
+                Loading => "Loading",
+                Update => "Update",
+                AddToCalendar => "Add to Calendar",
+                Intermission => "INT",
+                Final => "Final",
+                Overtime => "OT",
+                TimeTbd => "TBD",
+                NoGames => "No games scheduled.",
+                Offseason => "It's the offseason.",
+                NotifyFavorites => "Notify me about favorite teams",
+                GameStart => "Game start",
+                Goals => "Goals",
+                Intermissions => "Intermissions",
+                FinalScore => "Final",
+                GoalHorn => "Goal horn",
+                Language => "Language",
+                Leagues => "Leagues",
+                AllLeagues => "All leagues",
+                AllGames => "All games",
+                RatingsUnavailable => "Ratings not available",
+                GameUnavailable => "Game not available",
+                PowerRatings => "Power ratings",
+                Team => "Team",
+                Rating => "Rating",
+                NoPlays => "None",
+                BoxScore => "Box score",
+                Goalies => "Goalies",
+                ThreeStars => "Three stars",
+                Scoring => "Scoring",
+                Penalties => "Penalties",
+                GordleHelper => "Gordle Helper",
+                GordleInstructions => {
+                    "Enter each guess, then click its tiles to match the colors Gordle showed."
+                }
+                Letters => "Letters",
+                Remove => "Remove",
+                Guess => "Guess",
+                Add => "Add",
+                Reset => "Reset",
+                TryNext => "Try next",
+                Possible => "possible",
+                DailyGordle => "Daily Gordle",
+                GotIt => "Got it: ",
+                AnswerWas => "Today's name was ",
+                Copied => "Copied",
+                CopyResult => "Copy result",
+                Statistics => "Statistics",
+                Name => "Name",
+                FavoritesOnly => "Favorite teams only",
+                WinChance => "Chance to win, from results, home ice and rest",
+                QuestionsComments => "Questions, comments? Send an email to ",
+                NotFound => "Not found",
+                TodaysGames => "Today's games",
+                ThisDayInHistory => "This day in history",
+                Preseason => "Preseason",
+                Playoffs => "Playoffs",
+                AllStar => "All-Star",
+                WomensAllStar => "Women's All-Star",
+                Olympics => "Olympics",
+                WorldCup => "World Cup",
+                Exhibition => "Exhibition",
+                Favorite => "favorite",
+                WentToOvertime => "overtime",
+                Blowout => "blowout",
+                HighScoring => "high scoring",
+            },
+            Locale::French => match label {
+                Live => "En cours",
+                Upcoming => "À venir",
+                Finished => "Terminés",
+                Postponed => "Reportés",
+                Loading => "Chargement",
+                Update => "Actualiser",
+                AddToCalendar => "Ajouter au calendrier",
+                Intermission => "ENT",
+                Final => "Final",
+                Overtime => "prol.",
+                TimeTbd => "heure à déterminer",
+                NoGames => "Aucun match prévu.",
+                Offseason => "C'est la saison morte.",
+                NotifyFavorites => "M'avertir pour mes équipes favorites",
+                GameStart => "Début du match",
+                Goals => "Buts",
+                Intermissions => "Entractes",
+                FinalScore => "Fin du match",
+                GoalHorn => "Sirène de but",
+                Language => "Langue",
+                Leagues => "Ligues",
+                AllLeagues => "Toutes les ligues",
+                AllGames => "Tous les matchs",
+                RatingsUnavailable => "Classement non disponible",
+                GameUnavailable => "Match non disponible",
+                PowerRatings => "Classement de puissance",
+                Team => "Équipe",
+                Rating => "Cote",
+                NoPlays => "Aucun",
+                BoxScore => "Sommaire du match",
+                Goalies => "Gardiens",
+                ThreeStars => "Trois étoiles",
+                Scoring => "Buts marqués",
+                Penalties => "Pénalités",
+                GordleHelper => "Aide Gordle",
+                GordleInstructions => {
+                    "Entrez chaque essai, puis cliquez sur ses cases pour reprendre les couleurs de Gordle."
+                }
+                Letters => "Lettres",
+                Remove => "Retirer",
+                Guess => "Essai",
+                Add => "Ajouter",
+                Reset => "Recommencer",
+                TryNext => "Essayez ensuite",
+                Possible => "possible",
+                DailyGordle => "Gordle du jour",
+                GotIt => "Trouvé : ",
+                AnswerWas => "Le nom du jour était ",
+                Copied => "Copié",
+                CopyResult => "Copier le résultat",
+                Statistics => "Statistiques",
+                Name => "Nom",
+                FavoritesOnly => "Équipes favorites seulement",
+                WinChance => "Chances de victoire, selon les résultats, la glace et le repos",
+                QuestionsComments => "Questions, commentaires? Écrivez à ",
+                NotFound => "Page introuvable",
+                TodaysGames => "Matchs du jour",
+                ThisDayInHistory => "Ce jour-là dans l'histoire",
+                Preseason => "Préparatoire",
+                Playoffs => "Séries",
+                AllStar => "Match des étoiles",
+                WomensAllStar => "Match des étoiles féminin",
+                Olympics => "Jeux olympiques",
+                WorldCup => "Coupe du monde",
+                Exhibition => "Hors concours",
+                Favorite => "favori",
+                WentToOvertime => "prolongation",
+                Blowout => "victoire écrasante",
+                HighScoring => "festival offensif",
+            },
+        }
+    }
+
+    /// "7 games"
+    pub fn games_count(&self, count: usize) -> String {
+        match self {
+            Locale::English => format!("{} games", count),
+            Locale::French => format!("{} matchs", count),
+        }
+    }
+
+    pub fn season_cancelled(&self, season_label: &str) -> String {
+        match self {
+            Locale::English => format!("The {} season was cancelled.", season_label),
+            Locale::French => format!("La saison {} a été annulée.", season_label),
+        }
+    }
+
+    /// "Last 10", the heading for a team's recent change in rating.
+    pub fn last_games(&self, count: usize) -> String {
+        match self {
+            Locale::English => format!("Last {}", count),
+            Locale::French => format!("{} derniers", count),
+        }
+    }
+
+    /// How the power ratings are worked out, under the table.
+    pub fn ratings_note(&self, home_ice: f64, regression: f64, initial: f64) -> String {
+        match self {
+            Locale::English => format!(
+                "Elo ratings from every regular season and playoff game, with {:.0} points for home ice, bigger moves for bigger wins and {:.0}% of the way back to {:.0} between seasons.",
+                home_ice,
+                regression * 100.0,
+                initial
+            ),
+            Locale::French => format!(
+                "Cotes Elo tirées de chaque match de saison régulière et des séries, avec {:.0} points pour l'avantage de la glace, des écarts plus grands pour les victoires plus nettes et un retour de {:.0} % vers {:.0} entre les saisons.",
+                home_ice,
+                regression * 100.0,
+                initial
+            ),
+        }
+    }
+
+    /// "7 days", how far ahead a calendar download reaches.
+    pub fn days(&self, count: i64) -> String {
+        match (self, count) {
+            (Locale::English, 1) => "1 day".to_string(),
+            (Locale::English, _) => format!("{} days", count),
+            (Locale::French, 1) => "1 jour".to_string(),
+            (Locale::French, _) => format!("{} jours", count),
+        }
+    }
+
+    /// "Rest: EDM (SJS on a back-to-back)"
+    pub fn rest_back_to_back(&self, rested: &str, tired: &str) -> String {
+        match self {
+            Locale::English => format!("Rest: {} ({} on a back-to-back)", rested, tired),
+            Locale::French => format!("Repos : {} ({} joue deux soirs de suite)", rested, tired),
+        }
+    }
+
+    /// "Rest: EDM (3 days off vs 1)"
+    pub fn rest_days_off(&self, rested: &str, days: i64, other_days: i64) -> String {
+        match self {
+            Locale::English => format!("Rest: {} ({} days off vs {})", rested, days, other_days),
+            Locale::French => format!(
+                "Repos : {} ({} jours de congé contre {})",
+                rested, days, other_days
+            ),
+        }
+    }
+
+    /// "Puck drop: EDM @ SJS", a notification title.
+    pub fn puck_drop(&self, teams: &str) -> String {
+        match self {
+            Locale::English => format!("Puck drop: {}", teams),
+            Locale::French => format!("Mise au jeu : {}", teams),
+        }
+    }
+
+    /// "Sharks goal!"
+    pub fn goal_for(&self, team: &str) -> String {
+        match self {
+            Locale::English => format!("{} goal!", team),
+            Locale::French => format!("But des {}!", team),
+        }
+    }
+
+    /// "End of the 1st", with an ordinal from the API.
+    pub fn period_ended(&self, ordinal: &str) -> String {
+        match self {
+            Locale::English => format!("End of the {}", ordinal),
+            Locale::French => format!("Fin de la {}", self.period_ordinal(ordinal)),
+        }
+    }
+
+    /// "2nd period underway", with an ordinal from the API.
+    pub fn period_underway(&self, ordinal: &str) -> String {
+        match self {
+            Locale::English => format!("{} period underway", ordinal),
+            Locale::French => format!("{} période en cours", self.period_ordinal(ordinal)),
+        }
+    }
+
+    /// "12 possible names"
+    pub fn possible_names(&self, count: usize) -> String {
+        match self {
+            Locale::English => format!("{} possible names", count),
+            Locale::French => format!("{} noms possibles", count),
+        }
+    }
+
+    pub fn daily_gordle_rules(&self, tries: usize) -> String {
+        match self {
+            Locale::English => format!("Guess today's player last name in {} tries.", tries),
+            Locale::French => format!(
+                "Devinez le nom de famille du joueur du jour en {} essais.",
+                tries
+            ),
+        }
+    }
+
+    /// "Played 12 · Win 83% · Streak 4 · Best streak 6"
+    pub fn gordle_stats(&self, played: usize, win_percent: usize, streak: usize, best: usize) -> String {
+        match self {
+            Locale::English => format!(
+                "Played {} · Win {}% · Streak {} · Best streak {}",
+                played, win_percent, streak, best
+            ),
+            Locale::French => format!(
+                "Parties {} · Victoires {} % · Série {} · Meilleure série {}",
+                played, win_percent, streak, best
+            ),
+        }
+    }
+
+    /// Translates the API's period ordinals: "1st", "OT", "SO", "2OT".
+    pub fn period_ordinal(&self, ordinal: &str) -> String {
+        if *self == Locale::English {
+            return ordinal.to_string();
+        }
+        match ordinal {
+            "1st" => "1re".to_string(),
+            "2nd" => "2e".to_string(),
+            "3rd" => "3e".to_string(),
+            "OT" => "prol.".to_string(),
+            "SO" => "TB".to_string(),
+            _ => match ordinal.strip_suffix("OT").map(str::parse::<usize>) {
+                Some(Ok(number)) => format!("{}e prol.", number),
+                _ => ordinal.to_string(),
+            },
+        }
+    }
+
+    /// "7:00 PM" in English and "19 h 00" in French.
+    pub fn time(&self, time: &impl Timelike, twenty_four_hour: bool) -> String {
+        match (self, twenty_four_hour) {
+            (_, false) => {
+                let (pm, hour) = time.hour12();
+                let pm_str = if pm { "PM" } else { "AM" };
+                format!("{:\u{a0}>2}:{:02} {}", hour, time.minute(), pm_str)
+            }
+            (Locale::English, true) => format!("{:02}:{:02}", time.hour(), time.minute()),
+            (Locale::French, true) => format!("{} h {:02}", time.hour(), time.minute()),
+        }
+    }
+
+    /// Whether the locale usually writes times on a 24-hour clock.
+    pub fn twenty_four_hour(&self) -> bool {
+        *self == Locale::French
+    }
+
+    /// "Sunday, November 20, 2022" or "dimanche 20 novembre 2022"
+    pub fn long_date(&self, date: NaiveDate) -> String {
+        match self {
+            Locale::English => date.format("%A, %B %-d, %Y").to_string(),
+            Locale::French => format!(
+                "{} {} {} {}",
+                FRENCH_WEEKDAYS[date.weekday().num_days_from_monday() as usize],
+                french_day(date),
+                FRENCH_MONTHS[date.month0() as usize],
+                date.year()
+            ),
+        }
+    }
+
+    /// "20-Nov-2022" or "20 nov. 2022"
+    pub fn short_date(&self, date: NaiveDate) -> String {
+        match self {
+            Locale::English => date.format("%v").to_string(),
+            Locale::French => format!(
+                "{} {} {}",
+                french_day(date),
+                FRENCH_SHORT_MONTHS[date.month0() as usize],
+                date.year()
+            ),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Error> {
+        match Locale::ALL.iter().find(|locale| locale.code() == text) {
+            Some(locale) => Ok(*locale),
+            None => bail!("unknown locale '{}', expected en or fr", text),
+        }
+    }
+}
+
+/// French writes the first of the month as "1er".
+fn french_day(date: NaiveDate) -> String {
+    if date.day() == 1 {
+        "1er".to_string()
+    } else {
+        date.day().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_french() {
+        assert_eq!(Locale::from_language_tag("fr-CA"), Locale::French);
+        assert_eq!(Locale::from_language_tag("en-US"), Locale::English);
+        assert_eq!(Locale::French.text(Label::Upcoming), "À venir");
+        assert_eq!(Locale::French.period_ordinal("1st"), "1re");
+        assert_eq!(Locale::French.period_ordinal("3OT"), "3e prol.");
+        assert_eq!(Locale::English.period_ordinal("2nd"), "2nd");
+        assert_eq!(Locale::French.last_games(10), "10 derniers");
+        assert_eq!(Locale::French.possible_names(3), "3 noms possibles");
+
+        let date = NaiveDate::from_ymd_opt(2022, 11, 20).unwrap();
+        assert_eq!(Locale::French.long_date(date), "dimanche 20 novembre 2022");
+        assert_eq!(Locale::English.long_date(date), "Sunday, November 20, 2022");
+        let first = NaiveDate::from_ymd_opt(2023, 2, 1).unwrap();
+        assert_eq!(Locale::French.short_date(first), "1er févr. 2023");
+
+        let time = chrono::NaiveTime::from_hms_opt(19, 5, 0).unwrap();
+        assert_eq!(Locale::French.time(&time, true), "19 h 05");
+        assert_eq!(Locale::English.time(&time, false), "\u{a0}7:05 PM");
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{franchise, teams, Game, GameState, TeamAtGame};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamScore {
+    pub id: usize,
+    pub name: String,
+    pub abbreviation: Option<String>,
+    pub score: usize,
+}
+
+impl From<&TeamAtGame> for TeamScore {
+    fn from(team: &TeamAtGame) -> Self {
+        Self {
+            id: team.team.id,
+            name: team.team.display_name().to_string(),
+            abbreviation: franchise::abbreviation(team.team.id).map(str::to_string),
+            score: team.score,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Clock {
+    pub period: usize,
+    pub period_ordinal: String,
+    pub time_remaining: String,
+    pub in_intermission: bool,
+}
+
+/// A game in the cleaned-up shape served to other tools, in place of the
+/// raw NHL API response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedGame {
+    pub game_pk: usize,
+    pub start: DateTime<Utc>,
+    pub start_time_tbd: bool,
+    pub game_type: String,
+    pub state: GameState,
+    pub away: TeamScore,
+    pub home: TeamScore,
+    pub venue: Option<String>,
+    /// Only present while the game is live.
+    pub clock: Option<Clock>,
+}
+
+impl From<&Game> for NormalizedGame {
+    fn from(game: &Game) -> Self {
+        let clock = if game.is_live() {
+            let linescore = &game.linescore;
+            let intermission = &linescore.intermission_info;
+            let time_remaining = if intermission.in_intermission {
+                format!(
+                    "{}:{:02}",
+                    intermission.intermission_time_remaining / 60,
+                    intermission.intermission_time_remaining % 60
+                )
+            } else {
+                linescore.current_period_time_remaining.clone()
+            };
+            Some(Clock {
+                period: linescore.current_period,
+                period_ordinal: linescore.current_period_ordinal.clone(),
+                time_remaining,
+                in_intermission: intermission.in_intermission,
+            })
+        } else {
+            None
+        };
+        Self {
+            game_pk: game.game_pk,
+            start: game.game_date,
+            start_time_tbd: game.is_tbd(),
+            game_type: game.game_type.clone(),
+            state: game.state(),
+            away: (&game.teams.away).into(),
+            home: (&game.teams.home).into(),
+            venue: teams::team_info(game.teams.home.team.id).map(|team| team.venue.location()),
+            clock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+
+    #[test]
+    fn test_normalized_games() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let games: Vec<NormalizedGame> = schedule.dates[0].games.iter().map(Into::into).collect();
+
+        let final_game = &games[0];
+        assert_eq!(final_game.state, GameState::Final);
+        assert_eq!(final_game.away.abbreviation.as_deref(), Some("EDM"));
+        assert_eq!(final_game.home.score, 4);
+        assert_eq!(final_game.clock, None);
+
+        let intermission = games[3].clock.as_ref().unwrap();
+        assert!(intermission.in_intermission);
+        assert_eq!(intermission.time_remaining, "12:34");
+
+        let json = serde_json::to_value(&games[2]).unwrap();
+        assert_eq!(json["state"], "live");
+        assert_eq!(json["clock"]["periodOrdinal"], "2nd");
+        assert_eq!(json["venue"], "American Airlines Center, Dallas");
+    }
+}
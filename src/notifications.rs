@@ -0,0 +1,249 @@
+use crate::{
+    locale::{Label, Locale},
+    teams, Game, GameState,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum EventKind {
+    Start,
+    Goal,
+    Intermission,
+    Final,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    Started,
+    Goal { team_id: usize, team_name: String },
+    IntermissionStarted,
+    IntermissionEnded,
+    Final,
+}
+
+impl GameEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            GameEvent::Started => EventKind::Start,
+            GameEvent::Goal { .. } => EventKind::Goal,
+            GameEvent::IntermissionStarted | GameEvent::IntermissionEnded => {
+                EventKind::Intermission
+            }
+            GameEvent::Final => EventKind::Final,
+        }
+    }
+
+    /// Notification title and body for this event in `game`.
+    pub fn message(&self, game: &Game, locale: Locale) -> (String, String) {
+        let score = format!(
+            "{} {} @ {} {}",
+            game.teams.away.team.name,
+            game.teams.away.score,
+            game.teams.home.team.name,
+            game.teams.home.score
+        );
+        let ordinal = &game.linescore.current_period_ordinal;
+        let title = match self {
+            GameEvent::Started => locale.puck_drop(&game.describe_upcoming_teams()),
+            GameEvent::Goal { team_name, .. } => locale.goal_for(team_name),
+            GameEvent::IntermissionStarted => locale.period_ended(ordinal),
+            GameEvent::IntermissionEnded => locale.period_underway(ordinal),
+            GameEvent::Final => locale.text(Label::FinalScore).to_string(),
+        };
+        (title, score)
+    }
+}
+
+/// What changed in a game between two refreshes.
+pub fn game_events(previous: &Game, current: &Game) -> Vec<GameEvent> {
+    let mut events = vec![];
+    if previous.game_pk != current.game_pk {
+        return events;
+    }
+    let was_live = previous.state() == GameState::Live;
+    if current.is_live() && !was_live && !previous.is_finished() {
+        events.push(GameEvent::Started);
+    }
+    for (before, after) in [
+        (&previous.teams.away, &current.teams.away),
+        (&previous.teams.home, &current.teams.home),
+    ] {
+        for _ in before.score..after.score {
+            events.push(GameEvent::Goal {
+                team_id: after.team.id,
+                team_name: after.team.name.clone(),
+            });
+        }
+    }
+    let was_in_intermission = previous.linescore.intermission_info.in_intermission;
+    let in_intermission = current.linescore.intermission_info.in_intermission;
+    if current.is_live() {
+        if in_intermission && !was_in_intermission {
+            events.push(GameEvent::IntermissionStarted);
+        } else if was_in_intermission && !in_intermission {
+            events.push(GameEvent::IntermissionEnded);
+        }
+    }
+    if current.is_finished() && !previous.is_finished() {
+        events.push(GameEvent::Final);
+    }
+    events
+}
+
+/// Which alerts the user has opted in to, stored in the browser.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    pub start: bool,
+    pub goal: bool,
+    pub intermission: bool,
+    pub final_score: bool,
+    pub goal_horn: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: true,
+            goal: true,
+            intermission: false,
+            final_score: true,
+            goal_horn: false,
+        }
+    }
+}
+
+impl NotificationSettings {
+    pub fn wants(&self, kind: EventKind) -> bool {
+        self.enabled
+            && match kind {
+                EventKind::Start => self.start,
+                EventKind::Goal => self.goal,
+                EventKind::Intermission => self.intermission,
+                EventKind::Final => self.final_score,
+            }
+    }
+
+    pub fn toggle(&mut self, kind: EventKind) {
+        match kind {
+            EventKind::Start => self.start = !self.start,
+            EventKind::Goal => self.goal = !self.goal,
+            EventKind::Intermission => self.intermission = !self.intermission,
+            EventKind::Final => self.final_score = !self.final_score,
+        }
+    }
+}
+
+/// Events worth announcing, for games involving the favorite teams.
+pub fn favorite_events<'a>(
+    previous: &[Game],
+    current: &'a [Game],
+    settings: &NotificationSettings,
+) -> Vec<(&'a Game, GameEvent)> {
+    current
+        .iter()
+        .filter(|game| {
+            teams::FAVORITE_TEAM_IDS
+                .iter()
+                .any(|team_id| game.has_competitor(*team_id))
+        })
+        .filter_map(|game| {
+            let before = previous
+                .iter()
+                .find(|before| before.game_pk == game.game_pk)?;
+            Some((game, game_events(before, game)))
+        })
+        .flat_map(|(game, events)| events.into_iter().map(move |event| (game, event)))
+        .filter(|(_, event)| settings.wants(event.kind()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+
+    fn games() -> Vec<Game> {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        schedule.dates[0].games.clone()
+    }
+
+    fn live(game: &mut Game) {
+        game.status.abstract_game_state = "Live".to_string();
+        game.status.detailed_state = "In Progress".to_string();
+    }
+
+    #[test]
+    fn test_start_and_goals() {
+        let before = games()[4].clone();
+        let mut after = before.clone();
+        live(&mut after);
+        after.teams.home.score = 2;
+        assert_eq!(
+            game_events(&before, &after),
+            vec![
+                GameEvent::Started,
+                GameEvent::Goal {
+                    team_id: 54,
+                    team_name: "Vegas Golden Knights".to_string()
+                },
+                GameEvent::Goal {
+                    team_id: 54,
+                    team_name: "Vegas Golden Knights".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intermission_and_final() {
+        let before = games()[3].clone();
+        let mut after = before.clone();
+        after.linescore.intermission_info.in_intermission = false;
+        assert_eq!(
+            game_events(&before, &after),
+            vec![GameEvent::IntermissionEnded]
+        );
+        assert_eq!(
+            game_events(&after, &before),
+            vec![GameEvent::IntermissionStarted]
+        );
+        let (title, _) = GameEvent::IntermissionStarted.message(&before, Locale::English);
+        assert_eq!(title, "End of the 1st");
+        let (title, _) = GameEvent::IntermissionEnded.message(&before, Locale::French);
+        assert_eq!(title, "1re période en cours");
+
+        let mut finished = after.clone();
+        finished.status.abstract_game_state = "Final".to_string();
+        assert_eq!(game_events(&after, &finished), vec![GameEvent::Final]);
+        assert!(game_events(&finished, &finished).is_empty());
+    }
+
+    #[test]
+    fn test_favorite_events() {
+        let mut before = games();
+        let after = games();
+        before[0].status.abstract_game_state = "Live".to_string();
+        before[0].teams.home.score = 3;
+        before[1].status.abstract_game_state = "Live".to_string();
+
+        let mut settings = NotificationSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let events = favorite_events(&before, &after, &settings);
+        let kinds: Vec<_> = events.iter().map(|(_, event)| event.kind()).collect();
+        assert_eq!(kinds, vec![EventKind::Goal, EventKind::Final]);
+        assert_eq!(events[0].0.game_pk, 2022020275);
+
+        settings.toggle(EventKind::Goal);
+        assert_eq!(favorite_events(&before, &after, &settings).len(), 1);
+        settings.enabled = false;
+        assert!(favorite_events(&before, &after, &settings).is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    locale::Label,
+    pages::{
+        games_page::load_locale, route_link, DailyGordlePage, GamePage, GamesToday, GordlePage,
+        RatingsPage,
+    },
+};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Clone, Routable, PartialEq)]
+pub enum Route {
+    #[at("/")]
+    Home,
+    #[at("/game/:game_pk")]
+    Game { game_pk: usize },
+    #[at("/gordle")]
+    Gordle,
+    #[at("/gordle/daily")]
+    DailyGordle,
+    #[at("/ratings")]
+    Ratings,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+// yew 0.19's html! expands component props into statements clippy flags.
+#[allow(clippy::let_unit_value, clippy::unnecessary_operation)]
+fn switch(route: &Route) -> Html {
+    match route {
+        Route::Home => html! { <GamesToday /> },
+        Route::Game { game_pk } => html! { <GamePage game_pk={*game_pk} /> },
+        Route::Gordle => html! { <GordlePage /> },
+        Route::DailyGordle => html! { <DailyGordlePage /> },
+        Route::Ratings => html! { <RatingsPage /> },
+        Route::NotFound => {
+            let locale = load_locale();
+            html! {
+                <div class="container mt-4">
+                    <h1>{ locale.text(Label::NotFound) }</h1>
+                    { route_link(Route::Home, locale.text(Label::TodaysGames)) }
+                </div>
+            }
+        }
+    }
+}
+
+pub struct App;
+
+impl Component for App {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    #[allow(clippy::unnecessary_operation)]
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {
+            <BrowserRouter>
+                <Switch<Route> render={Switch::render(switch)} />
+            </BrowserRouter>
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    cache::{CacheStore, LocalStorageStore, KEY_PREFIX},
+    gordle::{DailyGame, GordleStats, MAX_GUESSES},
+    locale::{Label, Locale},
+    pages::{
+        games_page::{load_locale, questions_comments},
+        gordle_page::tile_class,
+        route_link, Route,
+    },
+};
+use chrono::Local;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+fn game_key() -> String {
+    format!("{}:gordle:daily", KEY_PREFIX)
+}
+
+fn stats_key() -> String {
+    format!("{}:gordle:stats", KEY_PREFIX)
+}
+
+fn load<T: for<'de> serde::Deserialize<'de>>(key: &str) -> Option<T> {
+    LocalStorageStore
+        .load(key)
+        .and_then(|text| serde_json::from_str(&text).ok())
+}
+
+fn save<T: serde::Serialize>(key: &str, value: &T) {
+    if let Ok(text) = serde_json::to_string(value) {
+        LocalStorageStore.store(key, &text);
+    }
+}
+
+/// `navigator.clipboard.writeText`, reached through reflection because
+/// web-sys only exposes the Clipboard API behind an unstable cfg.
+fn copy_to_clipboard(text: &str) {
+    let copy = || -> Result<(), JsValue> {
+        let navigator = web_sys::window()
+            .ok_or_else(|| JsValue::from_str("no window"))?
+            .navigator();
+        let clipboard = js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard"))?;
+        let write_text: js_sys::Function =
+            js_sys::Reflect::get(&clipboard, &JsValue::from_str("writeText"))?.dyn_into()?;
+        write_text.call1(&clipboard, &JsValue::from_str(text))?;
+        Ok(())
+    };
+    if let Err(err) = copy() {
+        log::info!("copy failed: {:?}", err);
+    }
+}
+
+fn stats_view(stats: &GordleStats, locale: Locale) -> Html {
+    let win_percent = (stats.won * 100).checked_div(stats.played).unwrap_or_default();
+    html! {
+        <div class="mt-3">
+            <h2>{ locale.text(Label::Statistics) }</h2>
+            <p>
+                { locale.gordle_stats(stats.played, win_percent, stats.current_streak, stats.max_streak) }
+            </p>
+            <ul>
+            {
+                for stats.distribution.iter().enumerate().map(|(index, count)| html! {
+                    <li>{ format!("{}: {}", index + 1, count) }</li>
+                })
+            }
+            </ul>
+        </div>
+    }
+}
+
+pub enum Msg {
+    WordChanged(String),
+    Guess,
+    Share,
+}
+
+/// Today's puzzle: one player's last name, the same for everyone.
+pub struct DailyGordlePage {
+    game: DailyGame,
+    stats: GordleStats,
+    word: String,
+    error: Option<String>,
+    copied: bool,
+    locale: Locale,
+}
+
+impl Component for DailyGordlePage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let today = Local::now().date_naive();
+        let game = load::<DailyGame>(&game_key())
+            .filter(|game| game.date == today)
+            .unwrap_or_else(|| DailyGame::new(today));
+        Self {
+            game,
+            stats: load(&stats_key()).unwrap_or_default(),
+            word: String::new(),
+            error: None,
+            copied: false,
+            locale: load_locale(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::WordChanged(word) => {
+                self.word = word;
+                self.error = None;
+                true
+            }
+            Msg::Guess => {
+                match self.game.guess(&self.word) {
+                    Ok(_) => {
+                        self.word.clear();
+                        save(&game_key(), &self.game);
+                        if self.game.is_over() {
+                            self.stats.record(&self.game);
+                            save(&stats_key(), &self.stats);
+                        }
+                    }
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+                true
+            }
+            Msg::Share => {
+                copy_to_clipboard(&self.game.share_text());
+                self.copied = true;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let rows = self.game.rows();
+        html! {
+            <div class="container mt-4">
+            { route_link(Route::Home, self.locale.text(Label::AllGames)) }
+            <h1>{ self.locale.text(Label::DailyGordle) }</h1>
+            <p>{ self.locale.daily_gordle_rules(MAX_GUESSES) }</p>
+            <div class="mb-3">
+            {
+                for rows.iter().map(|(word, tiles)| html! {
+                    <div class="d-flex mb-1">
+                    {
+                        for word.chars().zip(tiles.iter()).map(|(letter, tile)| html! {
+                            <span class={classes!(tile_class(*tile), "d-inline-flex", "align-items-center", "justify-content-center")}>
+                                { letter.to_uppercase().to_string() }
+                            </span>
+                        })
+                    }
+                    </div>
+                })
+            }
+            </div>
+            if self.game.is_over() {
+                <p class="lead">
+                    { self.locale.text(if self.game.is_won() { Label::GotIt } else { Label::AnswerWas }) }
+                    <strong>{ self.game.answer() }</strong>
+                </p>
+                <pre>{ self.game.share_text() }</pre>
+                <button class="btn btn-primary" onclick={ctx.link().callback(|_| Msg::Share)}>
+                    { self.locale.text(if self.copied { Label::Copied } else { Label::CopyResult }) }
+                </button>
+                { stats_view(&self.stats, self.locale) }
+            } else {
+                <form class="d-flex mb-3" onsubmit={ctx.link().callback(|e: FocusEvent| {
+                    e.prevent_default();
+                    Msg::Guess
+                })}>
+                    <input class="form-control w-auto" type="text" maxlength="5"
+                        placeholder={self.locale.text(Label::Name)} value={self.word.clone()}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            Msg::WordChanged(input.value())
+                        })} />
+                    <button class="btn btn-primary ms-2" type="submit">{ self.locale.text(Label::Guess) }</button>
+                </form>
+                if let Some(error) = &self.error {
+                    <p class="text-danger">{ error }</p>
+                }
+            }
+            { route_link(Route::Gordle, self.locale.text(Label::GordleHelper)) }
+            { questions_comments(self.locale) }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    api,
+    boxscore::{BoxScore, StatRow},
+    live::{LiveFeed, Play},
+    locale::{Label, Locale},
+    pages::{
+        games_page::{fetch_json, load_locale, questions_comments},
+        route_link, Route,
+    },
+};
+use anyhow::Error;
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+const REFRESH_MILLIS: u32 = 15_000;
+
+fn linescore_table(feed: &LiveFeed) -> Html {
+    let teams = &feed.game_data.teams;
+    let linescore = &feed.live_data.linescore;
+    html! {
+        <table class="table table-sm w-auto">
+            <thead>
+                <tr>
+                    <th></th>
+                    { for linescore.periods.iter().map(|period| html! { <th>{ &period.ordinal_num }</th> }) }
+                    <th>{ "T" }</th>
+                    <th>{ "SOG" }</th>
+                </tr>
+            </thead>
+            <tbody>
+                <tr>
+                    <th>{ &teams.away.abbreviation }</th>
+                    { for linescore.periods.iter().map(|period| html! { <td>{ period.away.goals }</td> }) }
+                    <td><strong>{ linescore.teams.away.goals }</strong></td>
+                    <td>{ linescore.teams.away.shots_on_goal }</td>
+                </tr>
+                <tr>
+                    <th>{ &teams.home.abbreviation }</th>
+                    { for linescore.periods.iter().map(|period| html! { <td>{ period.home.goals }</td> }) }
+                    <td><strong>{ linescore.teams.home.goals }</strong></td>
+                    <td>{ linescore.teams.home.shots_on_goal }</td>
+                </tr>
+            </tbody>
+        </table>
+    }
+}
+
+fn plays_by_period(
+    feed: &LiveFeed,
+    title: &str,
+    plays: &[&Play],
+    describe: fn(&Play) -> String,
+    locale: Locale,
+) -> Html {
+    html! {
+        <div>
+        <h2>{ title }</h2>
+        if plays.is_empty() {
+            <p>{ locale.text(Label::NoPlays) }</p>
+        }
+        {
+            for feed.by_period(plays).into_iter().map(|(ordinal, plays)| html! {
+                <div>
+                <h3 class="h5">{ ordinal }</h3>
+                <ul>
+                {
+                    for plays.iter().map(|play| html! {
+                        <li>
+                            { format!(
+                                "{} {} {}",
+                                play.about.period_time,
+                                play.team.as_ref().map(|team| team.tri_code.as_str()).unwrap_or_default(),
+                                describe(play)
+                            ) }
+                        </li>
+                    })
+                }
+                </ul>
+                </div>
+            })
+        }
+        </div>
+    }
+}
+
+fn box_score_view(box_score: &BoxScore, locale: Locale) -> Html {
+    let row = |row: &StatRow| {
+        html! {
+            <tr><th>{ &row.label }</th><td>{ &row.away }</td><td>{ &row.home }</td></tr>
+        }
+    };
+    html! {
+        <div>
+        <h2>{ locale.text(Label::BoxScore) }</h2>
+        <table class="table table-sm w-auto">
+            <thead>
+                <tr><th></th><th>{ &box_score.away }</th><th>{ &box_score.home }</th></tr>
+            </thead>
+            <tbody>
+                { for box_score.stats.iter().map(row) }
+            </tbody>
+        </table>
+        <h3 class="h5">{ locale.text(Label::Goalies) }</h3>
+        <ul>
+            { for box_score.goalies.iter().map(|goalie| html! { <li>{ goalie.describe() }</li> }) }
+        </ul>
+        if !box_score.stars.is_empty() {
+            <h3 class="h5">{ locale.text(Label::ThreeStars) }</h3>
+            <ol>
+                { for box_score.stars.iter().map(|star| html! { <li>{ star }</li> }) }
+            </ol>
+        }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GamePageProps {
+    pub game_pk: usize,
+}
+
+pub enum Msg {
+    FetchReady(Result<Box<LiveFeed>, Error>),
+    Refresh,
+}
+
+/// Scoring plays, penalties and the period-by-period line for one game,
+/// refreshed while the game is live.
+pub struct GamePage {
+    feed: Option<Box<LiveFeed>>,
+    error: Option<String>,
+    locale: Locale,
+    _refresh: Interval,
+}
+
+impl GamePage {
+    fn fetch_feed(&self, ctx: &Context<Self>) {
+        let link = ctx.link().clone();
+        let uri = api::url(&api::live_feed_path(ctx.props().game_pk));
+        wasm_bindgen_futures::spawn_local(async move {
+            let feed = fetch_json::<LiveFeed>(&uri).await.map(Box::new);
+            link.send_message(Msg::FetchReady(feed));
+        });
+    }
+}
+
+impl Component for GamePage {
+    type Message = Msg;
+    type Properties = GamePageProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let page = Self {
+            feed: None,
+            error: None,
+            locale: load_locale(),
+            _refresh: Interval::new(REFRESH_MILLIS, move || link.send_message(Msg::Refresh)),
+        };
+        page.fetch_feed(ctx);
+        page
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.feed = None;
+        self.error = None;
+        self.fetch_feed(ctx);
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::FetchReady(Ok(feed)) => {
+                self.feed = Some(feed);
+                self.error = None;
+                true
+            }
+            Msg::FetchReady(Err(err)) => {
+                log::info!("live feed failed: {}", err);
+                self.error = Some(err.to_string());
+                true
+            }
+            Msg::Refresh => {
+                // Upcoming games are polled too, so the page picks up the
+                // opening faceoff; there's nothing left to fetch once final.
+                if self.feed.as_ref().is_some_and(|feed| !feed.is_finished()) {
+                    self.fetch_feed(ctx);
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let back = html! {
+            { route_link(Route::Home, self.locale.text(Label::AllGames)) }
+        };
+        let feed = match &self.feed {
+            Some(feed) => feed,
+            None => {
+                return html! {
+                    <div class="container mt-4">
+                    { back }
+                    <h2>{ self.locale.text(if self.error.is_some() { Label::GameUnavailable } else { Label::Loading }) }</h2>
+                    { questions_comments(self.locale) }
+                    </div>
+                }
+            }
+        };
+        let teams = &feed.game_data.teams;
+        let linescore = &feed.live_data.linescore;
+        let status = if feed.is_live() {
+            let period = self.locale.period_ordinal(&linescore.current_period_ordinal);
+            if linescore.intermission_info.in_intermission {
+                format!("{} {}", period, self.locale.text(Label::Intermission))
+            } else {
+                format!("{} {}", period, linescore.current_period_time_remaining)
+            }
+        } else {
+            feed.game_data.status.detailed_state.clone()
+        };
+        html! {
+            <div class="container mt-4">
+            { back }
+            <h1>
+                { format!(
+                    "{} {} @ {} {}",
+                    teams.away.name, linescore.teams.away.goals,
+                    teams.home.name, linescore.teams.home.goals
+                ) }
+            </h1>
+            <p class="lead">{ status }</p>
+            if let Some(power_play) = feed.power_play_state() {
+                <p class="text-danger">{ power_play }</p>
+            }
+            { linescore_table(feed) }
+            if feed.is_finished() {
+                if let Some(box_score) = BoxScore::from_feed(feed) {
+                    { box_score_view(&box_score, self.locale) }
+                }
+            }
+            { plays_by_period(
+                feed,
+                self.locale.text(Label::Scoring),
+                &feed.scoring_plays(),
+                Play::goal_summary,
+                self.locale,
+            ) }
+            { plays_by_period(
+                feed,
+                self.locale.text(Label::Penalties),
+                &feed.penalty_plays(),
+                Play::penalty_summary,
+                self.locale,
+            ) }
+            { questions_comments(self.locale) }
+            </div>
+        }
+    }
+}
@@ -1,10 +1,26 @@
-use crate::{Content, Game, NextGameSchedule};
+use crate::{
+    api,
+    cache::{Cache, CacheStore, LocalStorageStore, KEY_PREFIX},
+    ics::{self, GameFilter},
+    league::{self, League, LeagueGame},
+    locale::{Label, Locale},
+    notifications::{favorite_events, EventKind, NotificationSettings},
+    pages::{
+        history_panel::HistoryPanel, notifier, ratings_page::rating_games, route_link, Route,
+    },
+    ratings::Ratings,
+    season::Season,
+    travel::{self, RestComparison},
+    win_probability::WinModel,
+    Content, Game, GameGroups, NextGameSchedule,
+};
 use anyhow::Error;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
 use chrono_english::{parse_date_string, Dialect};
 use gloo_net::http::Request;
+use gloo_timers::callback::Interval;
 use std::collections::HashMap;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::{prelude::*, Component};
 
 type PreviewStrings = HashMap<usize, String>;
@@ -25,75 +41,569 @@ fn images_for_preview(game: &Game, previews: &PreviewStrings) -> Html {
         <>
         {
             for us_avail.iter().map(|broadcaster| html! {
-                <img alt = { format!("{}", broadcaster.trim()) } class="logo" src={ format!("/images/{}.png", broadcaster.trim().replace(" ", "_"))} />
+                <img alt = { broadcaster.trim().to_string() } class="logo" src={ format!("/images/{}.png", broadcaster.trim().replace(" ", "_"))} />
             })
         }
         </>
     }
 }
 
-fn questions_comments() -> Html {
+fn locale_key() -> String {
+    format!("{}:locale", KEY_PREFIX)
+}
+
+/// The saved choice, or the browser's language.
+pub(super) fn load_locale() -> Locale {
+    LocalStorageStore
+        .load(&locale_key())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .or_else(|| {
+            let language = web_sys::window()?.navigator().language()?;
+            Some(Locale::from_language_tag(&language))
+        })
+        .unwrap_or_default()
+}
+
+fn save_locale(locale: Locale) {
+    if let Ok(text) = serde_json::to_string(&locale) {
+        LocalStorageStore.store(&locale_key(), &text);
+    }
+}
+
+fn leagues_key() -> String {
+    format!("{}:leagues", KEY_PREFIX)
+}
+
+/// The leagues to show, just the NHL until some are picked.
+fn load_leagues() -> Vec<League> {
+    LocalStorageStore
+        .load(&leagues_key())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_else(|| vec![League::Nhl])
+}
+
+fn save_leagues(leagues: &[League]) {
+    if let Ok(text) = serde_json::to_string(leagues) {
+        LocalStorageStore.store(&leagues_key(), &text);
+    }
+}
+
+/// Day counts offered for the calendar download, starting on the shown day.
+const EXPORT_DAYS: [i64; 4] = [1, 7, 14, 30];
+
+/// What the calendar download covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CalendarExport {
+    days: i64,
+    favorites: bool,
+}
+
+impl Default for CalendarExport {
+    fn default() -> Self {
+        Self {
+            days: 1,
+            favorites: false,
+        }
+    }
+}
+
+fn calendar_download(
+    games: &[Game],
+    previews: &PreviewStrings,
+    date: &DateTime<Local>,
+    export: CalendarExport,
+    locale: Locale,
+) -> Html {
+    let filter = if export.favorites {
+        GameFilter::favorites()
+    } else {
+        GameFilter::All
+    };
+    let calendar = ics::calendar(games, previews, &filter, Utc::now());
+    let href = format!(
+        "data:text/calendar;charset=utf-8,{}",
+        String::from(js_sys::encode_uri_component(&calendar))
+    );
+    let start = date.date_naive();
+    let file_name = if export.days > 1 {
+        let end = start + Duration::days(export.days - 1);
+        format!("games-{}-{}.ics", start.format("%F"), end.format("%F"))
+    } else {
+        format!("games-{}.ics", start.format("%F"))
+    };
+    html! {
+        <a class="btn btn-secondary ms-2" href={href} download={file_name}>
+            { locale.text(Label::AddToCalendar) }
+        </a>
+    }
+}
+
+fn game_type_badge(game: &Game, locale: Locale) -> Html {
+    match game.game_type_label() {
+        Some(label) => html! { <span class="badge bg-info ms-2">{ locale.text(label) }</span> },
+        None => html! {},
+    }
+}
+
+fn rest_badge(game: &Game, season_games: &[Game], locale: Locale) -> Html {
+    match RestComparison::new(game, season_games).flag(locale) {
+        Some(flag) => html! { <span class="badge bg-warning text-dark ms-2">{ flag }</span> },
+        None => html! {},
+    }
+}
+
+fn win_badge(game: &Game, model: &WinModel, season_games: &[Game], locale: Locale) -> Html {
+    match model.predict(game, season_games) {
+        Some(prediction) => html! {
+            <span class="badge bg-secondary ms-2" title={locale.text(Label::WinChance)}>
+                { prediction.describe() }
+            </span>
+        },
+        None => html! {},
+    }
+}
+
+fn game_link(game: &Game, offset: f64, season_games: &[Game], locale: Locale) -> Html {
+    html! {
+        <>
+        { route_link(
+            Route::Game { game_pk: game.game_pk },
+            game.describe_with_series(offset, season_games, locale),
+        ) }
+        { game_type_badge(game, locale) }
+        </>
+    }
+}
+
+fn no_games_reason(date: &DateTime<Local>, locale: Locale) -> Html {
+    let day = date.date_naive();
+    let season = Season::for_date(day);
+    let reason = if season.cancelled() {
+        locale.season_cancelled(&season.label())
+    } else if Season::is_offseason(day) {
+        locale.text(Label::Offseason).to_string()
+    } else {
+        locale.text(Label::NoGames).to_string()
+    };
+    html! { <p class="lead">{ reason }</p> }
+}
+
+fn page_links(locale: Locale) -> Html {
     html! {
         <div class="mt-3">
-        { "Questions, comments? Send an email to " }
+            { route_link(Route::Ratings, locale.text(Label::PowerRatings)) }
+            { " · " }
+            { route_link(Route::DailyGordle, locale.text(Label::DailyGordle)) }
+            { " · " }
+            { route_link(Route::Gordle, locale.text(Label::GordleHelper)) }
+        </div>
+    }
+}
+
+pub(super) fn questions_comments(locale: Locale) -> Html {
+    html! {
+        <div class="mt-3">
+        { locale.text(Label::QuestionsComments) }
         <a href="mailto:rob@tsuk.com"> { "rob@tsuk.com" }</a>
         </div>
     }
 }
 
+const REFRESH_MILLIS: u32 = 30_000;
+
+fn notification_settings(
+    settings: &NotificationSettings,
+    link: &yew::html::Scope<GamesToday>,
+    locale: Locale,
+) -> Html {
+    let toggles = [
+        (EventKind::Start, locale.text(Label::GameStart), settings.start),
+        (EventKind::Goal, locale.text(Label::Goals), settings.goal),
+        (EventKind::Intermission, locale.text(Label::Intermissions), settings.intermission),
+        (EventKind::Final, locale.text(Label::FinalScore), settings.final_score),
+    ];
+    html! {
+        <div class="mt-3">
+            <div class="form-check form-switch">
+                <input class="form-check-input" type="checkbox" id="notify"
+                    checked={settings.enabled}
+                    onchange={link.callback(|_| Msg::ToggleNotifications)} />
+                <label class="form-check-label" for="notify">{ locale.text(Label::NotifyFavorites) }</label>
+            </div>
+            if settings.enabled {
+                <div class="ms-4">
+                {
+                    for toggles.iter().map(|(kind, label, checked)| {
+                        let kind = *kind;
+                        let id = format!("notify-{:?}", kind);
+                        html! {
+                            <div class="form-check form-check-inline">
+                                <input class="form-check-input" type="checkbox" id={id.clone()}
+                                    checked={*checked}
+                                    onchange={link.callback(move |_| Msg::ToggleEvent(kind))} />
+                                <label class="form-check-label" for={id}>{ *label }</label>
+                            </div>
+                        }
+                    })
+                }
+                    <div class="form-check form-check-inline">
+                        <input class="form-check-input" type="checkbox" id="notify-horn"
+                            checked={settings.goal_horn}
+                            onchange={link.callback(|_| Msg::ToggleGoalHorn)} />
+                        <label class="form-check-label" for="notify-horn">{ locale.text(Label::GoalHorn) }</label>
+                    </div>
+                </div>
+            }
+        </div>
+    }
+}
+
+fn calendar_options(
+    export: CalendarExport,
+    locale: Locale,
+    link: &yew::html::Scope<GamesToday>,
+) -> Html {
+    html! {
+        <div class="mb-3">
+            <label class="me-2" for="export-days">{ locale.text(Label::AddToCalendar) }</label>
+            <select id="export-days" class="form-select form-select-sm d-inline-block w-auto me-3"
+                onchange={link.callback(|e: Event| {
+                    let select: HtmlSelectElement = e.target_unchecked_into();
+                    Msg::ExportDays(select.value())
+                })}>
+            {
+                for EXPORT_DAYS.iter().map(|days| html! {
+                    <option value={days.to_string()} selected={*days == export.days}>{ locale.days(*days) }</option>
+                })
+            }
+            </select>
+            <div class="form-check form-check-inline">
+                <input class="form-check-input" type="checkbox" id="export-favorites"
+                    checked={export.favorites}
+                    onchange={link.callback(|_| Msg::ToggleExportFavorites)} />
+                <label class="form-check-label" for="export-favorites">{ locale.text(Label::FavoritesOnly) }</label>
+            </div>
+        </div>
+    }
+}
+
+fn language_picker(locale: Locale, link: &yew::html::Scope<GamesToday>) -> Html {
+    html! {
+        <div class="mt-3">
+            <label class="me-2" for="language">{ locale.text(Label::Language) }</label>
+            <select id="language" class="form-select form-select-sm d-inline-block w-auto"
+                onchange={link.callback(|e: Event| {
+                    let select: HtmlSelectElement = e.target_unchecked_into();
+                    Msg::LocaleChanged(select.value())
+                })}>
+            {
+                for Locale::ALL.iter().map(|option| html! {
+                    <option value={option.code()} selected={*option == locale}>{ option.name() }</option>
+                })
+            }
+            </select>
+        </div>
+    }
+}
+
+fn league_picker(leagues: &[League], locale: Locale, link: &yew::html::Scope<GamesToday>) -> Html {
+    html! {
+        <div class="mt-3">
+            <span class="me-2">{ locale.text(Label::Leagues) }</span>
+            {
+                for League::ALL.iter().map(|league| {
+                    let league = *league;
+                    let id = format!("league-{}", league.code());
+                    html! {
+                        <div class="form-check form-check-inline">
+                            <input class="form-check-input" type="checkbox" id={id.clone()}
+                                checked={leagues.contains(&league)}
+                                onchange={link.callback(move |_| Msg::ToggleLeague(league))} />
+                            <label class="form-check-label" for={id}>{ league.name() }</label>
+                        </div>
+                    }
+                })
+            }
+        </div>
+    }
+}
+
+/// Every selected league's games in start order, shown in place of the
+/// NHL sections once a league besides the NHL is picked. NHL games still
+/// link to their game page.
+fn league_timeline(games: &[LeagueGame], offset: f64, locale: Locale) -> Html {
+    let item = |game: &LeagueGame| {
+        let text = game.describe(offset, locale);
+        match game.id.parse() {
+            Ok(game_pk) if game.league == League::Nhl => route_link(Route::Game { game_pk }, text),
+            _ => text.into(),
+        }
+    };
+    html! {
+        <div>
+        <h2>{ locale.text(Label::AllLeagues) }</h2>
+        <ul>
+        {
+            for games.iter().map(|game| html! {
+                <li class={classes!(format!("league-{}", game.league.code()))}>{ item(game) }</li>
+            })
+        }
+        </ul>
+        </div>
+    }
+}
+
 #[allow(unused)]
 pub enum Msg {
     FetchReady(Result<NextGameSchedule, Error>),
+    /// Games before the day from the seasons the ratings use, for the day
+    /// they were fetched for.
+    SeasonReady(NaiveDate, Vec<Game>),
     PreviewReady(usize, String),
     DateChanged(String),
     UpdateButton,
+    Refresh,
+    ToggleNotifications,
+    PermissionResult(bool),
+    ToggleEvent(EventKind),
+    ToggleGoalHorn,
+    LocaleChanged(String),
+    ToggleLeague(League),
+    /// A league's games for the day they were fetched for.
+    LeagueReady(NaiveDate, League, Vec<LeagueGame>),
+    ExportDays(String),
+    ToggleExportFavorites,
+    /// Games for a calendar download starting on the day, for that many
+    /// days.
+    ExportReady(NaiveDate, i64, Vec<Game>),
 }
 
 pub struct GamesToday {
     schedule: Option<NextGameSchedule>,
+    /// Earlier games this season, for the season series and rest flags.
+    season_games: Vec<Game>,
+    /// Ratings from this season and the last, as on the ratings page, for
+    /// the upcoming games' chances.
+    win_model: WinModel,
     previews: PreviewStrings,
     date: DateTime<Local>,
     date_str: String,
+    notification_settings: NotificationSettings,
+    locale: Locale,
+    leagues: Vec<League>,
+    /// Games from the selected leagues other than the NHL.
+    league_games: HashMap<League, Vec<LeagueGame>>,
+    export: CalendarExport,
+    /// Games for a download longer than the shown day.
+    export_games: Vec<Game>,
+    _refresh: Interval,
+}
+
+pub(super) async fn fetch_json<T: for<'de> serde::Deserialize<'de>>(uri: &str) -> Result<T, Error> {
+    Ok(Request::get(uri).send().await?.json().await?)
+}
+
+/// A whole season's games, through the cache, falling back to a stale
+/// copy when the fetch fails. Cached games only keep what the ratings,
+/// rest and season series need.
+pub(super) async fn fetch_season_games(season: Season) -> Result<Vec<Game>, Error> {
+    let cache = Cache::new(LocalStorageStore);
+    let cached = cache.season(season, Utc::now());
+    match cached {
+        Some(cached) if cached.fresh => Ok(cached.value),
+        cached => {
+            let uri = api::url(&api::season_schedule_path(season, None));
+            match fetch_json::<NextGameSchedule>(&uri).await {
+                Ok(schedule) => {
+                    cache.put_season(season, &schedule, Utc::now());
+                    Ok(schedule_games(&schedule))
+                }
+                Err(err) => cached.map(|cached| cached.value).ok_or(err),
+            }
+        }
+    }
+}
+
+fn schedule_games(schedule: &NextGameSchedule) -> Vec<Game> {
+    schedule
+        .dates
+        .iter()
+        .flat_map(|date| date.games.iter().cloned())
+        .collect()
 }
 
 impl GamesToday {
+    fn announce(&self, schedule: &NextGameSchedule) {
+        let previous = match &self.schedule {
+            Some(previous) => schedule_games(previous),
+            None => return,
+        };
+        if !notifier::permission_granted() {
+            return;
+        }
+        let current = schedule_games(schedule);
+        let events = favorite_events(&previous, &current, &self.notification_settings);
+        for (game, event) in &events {
+            let (title, body) = event.message(game, self.locale);
+            notifier::notify(&format!("{}-{:?}", game.game_pk, event.kind()), &title, &body);
+        }
+        let goal = events
+            .iter()
+            .any(|(_, event)| event.kind() == EventKind::Goal);
+        if goal && self.notification_settings.goal_horn {
+            notifier::goal_horn();
+        }
+    }
+
+    /// Whether any shown game can still change. Games from late last night
+    /// are still live after midnight, so the date doesn't matter here.
+    fn should_refresh(&self) -> bool {
+        self.schedule.as_ref().is_some_and(|schedule| {
+            schedule_games(schedule)
+                .iter()
+                .any(|game| game.is_live() || game.is_preview())
+        })
+    }
+
+    fn fetch_season(&mut self, ctx: &Context<Self>) {
+        self.season_games.clear();
+        self.win_model = WinModel::new(Ratings::default());
+        let day = self.date.date_naive();
+        if Season::is_offseason(day) {
+            return;
+        }
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match rating_games(Season::for_date(day)).await {
+                Ok(games) => {
+                    let games = games
+                        .into_iter()
+                        .filter(|game| travel::local_date(game) < day)
+                        .collect();
+                    link.send_message(Msg::SeasonReady(day, games));
+                }
+                Err(err) => log::info!("season games: {}", err),
+            }
+        });
+    }
+
+    fn fetch_export(&mut self, ctx: &Context<Self>) {
+        self.export_games.clear();
+        let days = self.export.days;
+        if days <= 1 {
+            return;
+        }
+        let start = self.date.date_naive();
+        let end = start + Duration::days(days - 1);
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let uri = api::url(&api::schedule_range_path(start, end, None));
+            match fetch_json::<NextGameSchedule>(&uri).await {
+                Ok(schedule) => {
+                    link.send_message(Msg::ExportReady(start, days, schedule_games(&schedule)))
+                }
+                Err(err) => log::info!("calendar games: {}", err),
+            }
+        });
+    }
+
+    fn fetch_leagues(&mut self, ctx: &Context<Self>) {
+        let day = self.date.date_naive();
+        let today = Local::now().date_naive();
+        for league in self
+            .leagues
+            .iter()
+            .copied()
+            .filter(|league| *league != League::Nhl)
+        {
+            let link = ctx.link().clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let adapter = league.adapter();
+                let uri = adapter.schedule_url(day, today);
+                let text = match Request::get(&uri).send().await {
+                    Ok(response) => response.text().await,
+                    Err(err) => Err(err),
+                };
+                match text
+                    .map_err(Error::from)
+                    .and_then(|text| adapter.parse_schedule(&text, day))
+                {
+                    Ok(games) => link.send_message(Msg::LeagueReady(day, league, games)),
+                    Err(err) => log::info!("{} games: {}", league.name(), err),
+                }
+            });
+        }
+    }
+
+    /// The NHL games, when picked, and the other leagues' in start order.
+    fn timeline(&self, games: &[Game]) -> Vec<LeagueGame> {
+        let nhl = games
+            .iter()
+            .filter(|_| self.leagues.contains(&League::Nhl))
+            .map(LeagueGame::from);
+        let others = self
+            .leagues
+            .iter()
+            .filter_map(|league| self.league_games.get(league))
+            .flatten()
+            .cloned();
+        league::merged_timeline(nhl.chain(others).collect())
+    }
+
     fn fetch_schedule(&mut self, ctx: &Context<Self>) {
         let link = ctx.link().clone();
         let date = self.date;
         wasm_bindgen_futures::spawn_local(async move {
-            let fetched_schedule: NextGameSchedule = Request::get(&format!(
-                "https://statsapi.web.nhl.com/api/v1/schedule?expand=schedule.linescore&date={}",
-                date.format("%F")
-            ))
-            .send()
-            .await
-            .unwrap()
-            .json()
-            .await
-            .unwrap();
-
-            for date in &fetched_schedule.dates {
-                for game in &date.games {
-                    let game_pk = game.game_pk;
-                    let uri = format!("https://statsapi.web.nhl.com/{}", game.content.link);
-                    let preview_link = link.clone();
-                    wasm_bindgen_futures::spawn_local(async move {
-                        let content: Content = Request::get(&uri)
-                            .send()
-                            .await
-                            .unwrap()
-                            .json()
-                            .await
-                            .unwrap();
-                        preview_link.send_message(Msg::PreviewReady(
-                            game_pk,
-                            content.preview_string().unwrap_or_default(),
-                        ));
-                    });
+            let cache = Cache::new(LocalStorageStore);
+            let day = date.date_naive();
+            let cached = cache.schedule(day, Utc::now());
+            let fetched_schedule = match cached {
+                Some(cached) if cached.fresh => cached.value,
+                cached => {
+                    let uri = api::url(&api::schedule_path(day));
+                    match fetch_json::<NextGameSchedule>(&uri).await {
+                        Ok(schedule) => {
+                            cache.put_schedule(day, &schedule, Utc::now());
+                            schedule
+                        }
+                        Err(err) => match cached {
+                            Some(cached) => cached.value,
+                            None => {
+                                link.send_message(Msg::FetchReady(Err(err)));
+                                return;
+                            }
+                        },
+                    }
                 }
-            }
+            };
 
+            let games = schedule_games(&fetched_schedule);
             link.send_message(Msg::FetchReady(Ok(fetched_schedule)));
+
+            for game in games {
+                let preview_link = link.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let cache = Cache::new(LocalStorageStore);
+                    let cached = cache.preview(game.game_pk, Utc::now());
+                    let preview = match cached {
+                        Some(cached) if cached.fresh => Some(cached.value),
+                        cached => {
+                            let uri = api::url(&game.content.link);
+                            match fetch_json::<Content>(&uri).await {
+                                Ok(content) => {
+                                    let preview = content.preview_string().unwrap_or_default();
+                                    cache.put_preview(&game, &preview, Utc::now());
+                                    Some(preview)
+                                }
+                                Err(_) => cached.map(|cached| cached.value),
+                            }
+                        }
+                    };
+                    if let Some(preview) = preview {
+                        preview_link.send_message(Msg::PreviewReady(game.game_pk, preview));
+                    }
+                });
+            }
         });
     }
 }
@@ -105,13 +615,25 @@ impl Component for GamesToday {
     fn create(ctx: &Context<Self>) -> Self {
         let date_time_now: DateTime<Local> = Local::now();
         let date = date_time_now;
+        let link = ctx.link().clone();
         let mut gt = Self {
             schedule: None,
+            season_games: vec![],
+            win_model: WinModel::new(Ratings::default()),
             previews: Default::default(),
             date,
             date_str: date.format("%m/%d/%Y").to_string(),
+            notification_settings: notifier::load_settings(),
+            locale: load_locale(),
+            leagues: load_leagues(),
+            league_games: Default::default(),
+            export: Default::default(),
+            export_games: vec![],
+            _refresh: Interval::new(REFRESH_MILLIS, move || link.send_message(Msg::Refresh)),
         };
         gt.fetch_schedule(ctx);
+        gt.fetch_season(ctx);
+        gt.fetch_leagues(ctx);
         gt
     }
 
@@ -123,23 +645,121 @@ impl Component for GamesToday {
             }
             Msg::FetchReady(result) => {
                 if let Ok(schedule) = result {
+                    self.announce(&schedule);
+                    let games = schedule_games(&schedule);
+                    self.previews
+                        .retain(|game_pk, _| games.iter().any(|game| game.game_pk == *game_pk));
                     self.schedule = Some(schedule);
-                    self.previews = Default::default();
                     true
                 } else {
                     false
                 }
             }
+            Msg::SeasonReady(day, games) => {
+                if day != self.date.date_naive() {
+                    return false;
+                }
+                self.win_model = WinModel::new(Ratings::from_games(&games));
+                let season = Season::for_date(day).id();
+                self.season_games = games
+                    .into_iter()
+                    .filter(|game| game.season == season)
+                    .collect();
+                true
+            }
+            Msg::LeagueReady(day, league, games) => {
+                if day != self.date.date_naive() || !self.leagues.contains(&league) {
+                    return false;
+                }
+                self.league_games.insert(league, games);
+                true
+            }
+            Msg::ExportDays(days) => match days.parse() {
+                Ok(days) if days != self.export.days => {
+                    self.export.days = days;
+                    self.fetch_export(ctx);
+                    true
+                }
+                _ => false,
+            },
+            Msg::ToggleExportFavorites => {
+                self.export.favorites = !self.export.favorites;
+                true
+            }
+            Msg::ExportReady(start, days, games) => {
+                if start != self.date.date_naive() || days != self.export.days {
+                    return false;
+                }
+                self.export_games = games;
+                true
+            }
+            Msg::ToggleLeague(league) => {
+                if self.leagues.contains(&league) {
+                    self.leagues.retain(|selected| *selected != league);
+                } else {
+                    self.leagues.push(league);
+                    self.leagues.sort();
+                }
+                save_leagues(&self.leagues);
+                self.league_games.clear();
+                self.fetch_leagues(ctx);
+                true
+            }
             Msg::UpdateButton => {
                 self.fetch_schedule(ctx);
+                self.fetch_leagues(ctx);
                 false
             }
+            Msg::Refresh => {
+                if self.should_refresh() {
+                    self.fetch_schedule(ctx);
+                }
+                if self.date.date_naive() == Local::now().date_naive() {
+                    self.fetch_leagues(ctx);
+                }
+                false
+            }
+            Msg::ToggleNotifications => {
+                if self.notification_settings.enabled {
+                    self.notification_settings.enabled = false;
+                    notifier::save_settings(&self.notification_settings);
+                } else {
+                    notifier::request_permission(ctx.link().callback(Msg::PermissionResult));
+                }
+                true
+            }
+            Msg::PermissionResult(granted) => {
+                self.notification_settings.enabled = granted;
+                notifier::save_settings(&self.notification_settings);
+                true
+            }
+            Msg::ToggleEvent(kind) => {
+                self.notification_settings.toggle(kind);
+                notifier::save_settings(&self.notification_settings);
+                true
+            }
+            Msg::ToggleGoalHorn => {
+                self.notification_settings.goal_horn = !self.notification_settings.goal_horn;
+                notifier::save_settings(&self.notification_settings);
+                true
+            }
+            Msg::LocaleChanged(code) => {
+                if let Ok(locale) = code.parse() {
+                    self.locale = locale;
+                    save_locale(self.locale);
+                }
+                true
+            }
             Msg::DateChanged(date) => {
                 self.date_str = date.to_owned();
                 let date_only = parse_date_string(&self.date_str, Local::now(), Dialect::Us);
                 if let Ok(date_time) = date_only {
                     self.date = date_time;
+                    self.league_games.clear();
                     self.fetch_schedule(ctx);
+                    self.fetch_season(ctx);
+                    self.fetch_leagues(ctx);
+                    self.fetch_export(ctx);
                 } else {
                     log::info!("date = {}", self.date_str);
                 }
@@ -155,31 +775,49 @@ impl Component for GamesToday {
             let no_games = vec![];
             let games = schedule
                 .dates
-                .get(0)
-                .and_then(|date| Some(&date.games))
+                .first()
+                .map(|date| &date.games)
                 .unwrap_or(&no_games);
 
-            let finished: Vec<_> = games.iter().filter(|game| game.is_finished()).collect();
-            let live: Vec<_> = games.iter().filter(|game| game.is_live()).collect();
-            let preview: Vec<_> = games.iter().filter(|game| game.is_preview()).collect();
-            let postponed: Vec<_> = games.iter().filter(|game| game.is_postponed()).collect();
+            let groups = GameGroups::new(games);
             html! {
                 <div class="container mt-4">
                 <h1>
-                    { format!("{}: {} games", self.date.format("%F"), schedule.total_items) }
+                    { format!(
+                        "{}: {}",
+                        self.locale.long_date(self.date.date_naive()),
+                        self.locale.games_count(schedule.total_items)
+                    ) }
+                    <small class="text-muted ms-2">{ Season::for_date(self.date.date_naive()).label() }</small>
                     <button class="btn btn-primary ms-3" onclick={ctx.link().callback(|_| Msg::UpdateButton)}>
-                        { "Update" }
+                        { self.locale.text(Label::Update) }
                     </button>
+                    {
+                        calendar_download(
+                            if self.export.days > 1 { &self.export_games } else { games },
+                            &self.previews,
+                            &self.date,
+                            self.export,
+                            self.locale,
+                        )
+                    }
                 </h1>
+                { calendar_options(self.export, self.locale, ctx.link()) }
+                if games.is_empty() {
+                    { no_games_reason(&self.date, self.locale) }
+                }
+                if self.leagues.iter().any(|league| *league != League::Nhl) {
+                    { league_timeline(&self.timeline(games), offset, self.locale) }
+                } else if self.leagues.contains(&League::Nhl) {
                 {
-                    if live.len() > 0 {
+                    if !groups.live.is_empty() {
                         html! {
                             <div>
-                            <h2>{"Live"}</h2>
+                            <h2>{ self.locale.text(Label::Live) }</h2>
                             <ul>
                             {
-                                for live.iter().map(|game| html! {
-                                    <li class={classes!(game.class())}>{ game.describe(offset) }
+                                for groups.live.iter().map(|game| html! {
+                                    <li class={classes!(game.class())}>{ game_link(game, offset, &self.season_games, self.locale) }
                                     { images_for_preview(game, &self.previews) }
                                     </li>
                                 })
@@ -194,19 +832,21 @@ impl Component for GamesToday {
                         }
                 }
                 {
-                    if preview.len() > 0 {
+                    if !groups.preview.is_empty() {
                         html! {
                             <div>
                             <h2>
                                 {
-                                    "Upcoming"
+                                    self.locale.text(Label::Upcoming)
                                 }
                             </h2>
                             <ul>
                             {
-                                for preview .iter().map(|game| html! {
+                                for groups.preview.iter().map(|game| html! {
                                     <li class={classes!(game.class())}>
-                                    { game.describe(offset) }
+                                    { game_link(game, offset, &self.season_games, self.locale) }
+                                    { win_badge(game, &self.win_model, &self.season_games, self.locale) }
+                                    { rest_badge(game, &self.season_games, self.locale) }
                                     { images_for_preview(game, &self.previews) }
                                     </li>
                                 })
@@ -221,14 +861,14 @@ impl Component for GamesToday {
                         }
                 }
                 {
-                    if finished.len() > 0 {
+                    if !groups.finished.is_empty() {
                         html! {
                             <div>
-                            <h2>{"Finished"}</h2>
+                            <h2>{ self.locale.text(Label::Finished) }</h2>
                             <ul>
                             {
-                                for finished.iter().map(|game| html! {
-                                    <li class={classes!(game.class())}>{ game.describe(offset) }</li>
+                                for groups.finished.iter().map(|game| html! {
+                                    <li class={classes!(game.class())}>{ game_link(game, offset, &self.season_games, self.locale) }</li>
                                 })
                             }
                             </ul>
@@ -241,14 +881,14 @@ impl Component for GamesToday {
                         }
                 }
                 {
-                    if postponed.len() > 0 {
+                    if !groups.postponed.is_empty() {
                         html! {
                             <div>
-                            <h2>{"Postponed"}</h2>
+                            <h2>{ self.locale.text(Label::Postponed) }</h2>
                             <ul>
                             {
-                                for postponed.iter().map(|game| html! {
-                                    <li class={classes!(game.class())}>{ game.describe(offset) }</li>
+                                for groups.postponed.iter().map(|game| html! {
+                                    <li class={classes!(game.class())}>{ game.describe_localized(offset, self.locale) }</li>
                                 })
                             }
                             </ul>
@@ -259,6 +899,7 @@ impl Component for GamesToday {
                                 <div></div>
                             }
                         }
+                }
                 }
                     <input class="game_date"
                            id="date"
@@ -268,15 +909,20 @@ impl Component for GamesToday {
                             let input: HtmlInputElement = e.target_unchecked_into();
 
                             Msg::DateChanged(input.value())})}/>
-                { questions_comments() }
+                <HistoryPanel date={self.date.date_naive()} locale={self.locale} />
+                { notification_settings(&self.notification_settings, ctx.link(), self.locale) }
+                { league_picker(&self.leagues, self.locale, ctx.link()) }
+                { language_picker(self.locale, ctx.link()) }
+                { page_links(self.locale) }
+                { questions_comments(self.locale) }
                 </div>
             }
         } else {
             html! {
                 <div class="container mt-4">
                 <h1>{ "Games Today" }</h1>
-                <h2>{ "Loading" }</h2>
-                { questions_comments() }
+                <h2>{ self.locale.text(Label::Loading) }</h2>
+                { questions_comments(self.locale) }
                 </div>
             }
         }
@@ -0,0 +1,211 @@
+use crate::{
+    gordle::{last_names, GordleState, Ranking, Tile, DEFAULT_WORD_LENGTH, WORD_LENGTHS},
+    locale::{Label, Locale},
+    pages::{
+        games_page::{load_locale, questions_comments},
+        route_link, Route,
+    },
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+const SUGGESTION_COUNT: usize = 5;
+
+pub(crate) fn tile_class(tile: Tile) -> &'static str {
+    match tile {
+        Tile::Gray => "tile gray",
+        Tile::Yellow => "tile yellow",
+        Tile::Green => "tile green",
+    }
+}
+
+pub enum Msg {
+    LengthChanged(String),
+    WordChanged(String),
+    AddGuess,
+    CycleTile(usize, usize),
+    RemoveGuess(usize),
+    Reset,
+}
+
+/// Guesses entered as words with tiles clicked to the colors Gordle
+/// showed, and the player names that still fit.
+pub struct GordlePage {
+    /// Letters in the answer; changing it starts over.
+    length: usize,
+    guesses: Vec<(String, Vec<Tile>)>,
+    word: String,
+    locale: Locale,
+}
+
+impl GordlePage {
+    fn state(&self) -> GordleState {
+        let mut state = GordleState::with_length(self.length);
+        for (word, tiles) in &self.guesses {
+            if let Err(err) = state.guess(word, tiles.clone()) {
+                log::info!("skipping guess: {}", err);
+            }
+        }
+        state
+    }
+
+    fn word_is_valid(&self) -> bool {
+        self.word.chars().count() == self.length
+    }
+
+    fn guess_rows(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="mb-3">
+            {
+                for self.guesses.iter().enumerate().map(|(row, (word, tiles))| html! {
+                    <div class="d-flex align-items-center mb-1">
+                    {
+                        for word.chars().zip(tiles.iter()).enumerate().map(|(column, (letter, tile))| html! {
+                            <button class={tile_class(*tile)}
+                                onclick={ctx.link().callback(move |_| Msg::CycleTile(row, column))}>
+                                { letter.to_uppercase().to_string() }
+                            </button>
+                        })
+                    }
+                        <button class="btn btn-sm btn-outline-secondary ms-2"
+                            onclick={ctx.link().callback(move |_| Msg::RemoveGuess(row))}>
+                            { self.locale.text(Label::Remove) }
+                        </button>
+                    </div>
+                })
+            }
+            </div>
+        }
+    }
+}
+
+impl Component for GordlePage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            length: DEFAULT_WORD_LENGTH,
+            guesses: vec![],
+            word: String::new(),
+            locale: load_locale(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LengthChanged(length) => match length.parse() {
+                Ok(length) if length != self.length => {
+                    self.length = length;
+                    self.guesses.clear();
+                    self.word.clear();
+                    true
+                }
+                _ => false,
+            },
+            Msg::WordChanged(word) => {
+                self.word = word.trim().to_string();
+                true
+            }
+            Msg::AddGuess => {
+                if !self.word_is_valid() {
+                    return false;
+                }
+                let word = std::mem::take(&mut self.word);
+                self.guesses.push((word, vec![Tile::Gray; self.length]));
+                true
+            }
+            Msg::CycleTile(row, column) => {
+                if let Some(tile) = self
+                    .guesses
+                    .get_mut(row)
+                    .and_then(|(_, tiles)| tiles.get_mut(column))
+                {
+                    *tile = tile.cycle();
+                }
+                true
+            }
+            Msg::RemoveGuess(row) => {
+                if row < self.guesses.len() {
+                    self.guesses.remove(row);
+                }
+                true
+            }
+            Msg::Reset => {
+                self.guesses.clear();
+                self.word.clear();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let state = self.state();
+        let candidates = state.candidates();
+        let suggestions = if candidates.len() > 1 {
+            state.suggestions(last_names(self.length), Ranking::Entropy, SUGGESTION_COUNT)
+        } else {
+            vec![]
+        };
+        html! {
+            <div class="container mt-4">
+            { route_link(Route::Home, self.locale.text(Label::AllGames)) }
+            <h1>{ self.locale.text(Label::GordleHelper) }</h1>
+            <p>{ self.locale.text(Label::GordleInstructions) }</p>
+            <div class="mb-3">
+                <label class="me-2" for="length">{ self.locale.text(Label::Letters) }</label>
+                <select id="length" class="form-select form-select-sm d-inline-block w-auto"
+                    onchange={ctx.link().callback(|e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        Msg::LengthChanged(select.value())
+                    })}>
+                {
+                    for WORD_LENGTHS.iter().map(|length| html! {
+                        <option value={length.to_string()} selected={*length == self.length}>{ length }</option>
+                    })
+                }
+                </select>
+            </div>
+            { self.guess_rows(ctx) }
+            <form class="d-flex mb-3" onsubmit={ctx.link().callback(|e: FocusEvent| {
+                e.prevent_default();
+                Msg::AddGuess
+            })}>
+                <input class="form-control w-auto" type="text" maxlength={self.length.to_string()}
+                    placeholder={self.locale.text(Label::Guess)} value={self.word.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        Msg::WordChanged(input.value())
+                    })} />
+                <button class="btn btn-primary ms-2" type="submit" disabled={!self.word_is_valid()}>
+                    { self.locale.text(Label::Add) }
+                </button>
+                <button class="btn btn-secondary ms-2" type="button"
+                    onclick={ctx.link().callback(|_| Msg::Reset)}>
+                    { self.locale.text(Label::Reset) }
+                </button>
+            </form>
+            if !suggestions.is_empty() {
+                <h2>{ self.locale.text(Label::TryNext) }</h2>
+                <ul>
+                {
+                    for suggestions.iter().map(|suggestion| html! {
+                        <li>
+                            { format!("{} ({:.2} bits)", suggestion.word, suggestion.score) }
+                            if suggestion.candidate {
+                                <span class="badge bg-success ms-2">{ self.locale.text(Label::Possible) }</span>
+                            }
+                        </li>
+                    })
+                }
+                </ul>
+            }
+            <h2>{ self.locale.possible_names(candidates.len()) }</h2>
+            <ul class="candidates">
+                { for candidates.iter().map(|name| html! { <li>{ name }</li> }) }
+            </ul>
+            { questions_comments(self.locale) }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    api,
+    cache::{Cache, LocalStorageStore},
+    history::{describe_result, notable_results, this_day_dates},
+    locale::{Label, Locale},
+    pages::{games_page::fetch_json, route_link, Route},
+    season::Season,
+    Game, NextGameSchedule,
+};
+use chrono::{NaiveDate, Utc};
+use yew::prelude::*;
+
+const YEARS_BACK: usize = 10;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub date: NaiveDate,
+    pub locale: Locale,
+}
+
+pub enum Msg {
+    YearReady(usize, NaiveDate, Vec<Game>),
+}
+
+/// "This day in history": notable results from the same calendar date in
+/// earlier years.
+pub struct HistoryPanel {
+    years: Vec<(NaiveDate, Vec<Game>)>,
+    date: NaiveDate,
+    /// Bumped on every fetch, so answers for an earlier date are dropped
+    /// even after the user comes back to it.
+    generation: usize,
+}
+
+impl HistoryPanel {
+    fn fetch_years(&mut self, ctx: &Context<Self>) {
+        self.date = ctx.props().date;
+        self.years.clear();
+        self.generation += 1;
+        let generation = self.generation;
+        for day in this_day_dates(ctx.props().date, YEARS_BACK) {
+            if Season::is_offseason(day) {
+                continue;
+            }
+            let link = ctx.link().clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let cache = Cache::new(LocalStorageStore);
+                let schedule = match cache.schedule(day, Utc::now()) {
+                    Some(cached) => cached.value,
+                    None => {
+                        let uri = api::url(&api::schedule_path(day));
+                        match fetch_json::<NextGameSchedule>(&uri).await {
+                            Ok(schedule) => {
+                                cache.put_schedule(day, &schedule, Utc::now());
+                                schedule
+                            }
+                            Err(err) => {
+                                log::info!("history {}: {}", day, err);
+                                return;
+                            }
+                        }
+                    }
+                };
+                let games = schedule
+                    .dates
+                    .into_iter()
+                    .flat_map(|date| date.games)
+                    .collect();
+                link.send_message(Msg::YearReady(generation, day, games));
+            });
+        }
+    }
+}
+
+impl Component for HistoryPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut panel = Self {
+            years: vec![],
+            date: ctx.props().date,
+            generation: 0,
+        };
+        panel.fetch_years(ctx);
+        panel
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        // A new locale only needs a redraw.
+        if ctx.props().date != self.date {
+            self.fetch_years(ctx);
+        }
+        true
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::YearReady(generation, day, games) => {
+                if generation != self.generation {
+                    return false;
+                }
+                self.years.push((day, games));
+                self.years.sort_by(|(a, _), (b, _)| b.cmp(a));
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let locale = ctx.props().locale;
+        let years: Vec<_> = self
+            .years
+            .iter()
+            .map(|(day, games)| (day, notable_results(games)))
+            .filter(|(_, results)| !results.is_empty())
+            .collect();
+        if years.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="mt-3">
+            <h2>{ locale.text(Label::ThisDayInHistory) }</h2>
+            {
+                for years.iter().map(|(day, results)| html! {
+                    <div>
+                    <h3 class="h5">{ format!("{} ({})", day.format("%Y"), Season::for_date(**day).label()) }</h3>
+                    <ul>
+                    {
+                        for results.iter().map(|(game, reasons)| html! {
+                            <li>
+                                { route_link(Route::Game { game_pk: game.game_pk }, describe_result(game, locale)) }
+                                {
+                                    for reasons.iter().map(|reason| html! {
+                                        <span class="badge bg-secondary ms-2">{ locale.text(reason.label()) }</span>
+                                    })
+                                }
+                            </li>
+                        })
+                    }
+                    </ul>
+                    </div>
+                })
+            }
+            </div>
+        }
+    }
+}
@@ -1,3 +1,28 @@
+
+mod app;
+mod daily_gordle_page;
+mod game_page;
 mod games_page;
+mod gordle_page;
+mod history_panel;
+mod notifier;
+mod ratings_page;
 
+pub use app::{App, Route};
+pub use daily_gordle_page::DailyGordlePage;
+pub use game_page::GamePage;
 pub use games_page::GamesToday;
+pub use gordle_page::GordlePage;
+pub use ratings_page::RatingsPage;
+
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// A router link. yew 0.19's html! expands a link's props into statements
+/// clippy flags, so links go through here.
+#[allow(clippy::unnecessary_operation)]
+pub(crate) fn route_link(route: Route, content: impl Into<Html>) -> Html {
+    html! {
+        <Link<Route> to={route}>{ content.into() }</Link<Route>>
+    }
+}
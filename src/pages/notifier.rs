@@ -0,0 +1,80 @@
+use crate::{
+    cache::{CacheStore, LocalStorageStore, KEY_PREFIX},
+    notifications::NotificationSettings,
+};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AudioContext, Notification, NotificationOptions, NotificationPermission, OscillatorType,
+};
+use yew::Callback;
+
+fn settings_key() -> String {
+    format!("{}:notifications", KEY_PREFIX)
+}
+
+pub fn load_settings() -> NotificationSettings {
+    LocalStorageStore
+        .load(&settings_key())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_settings(settings: &NotificationSettings) {
+    if let Ok(text) = serde_json::to_string(settings) {
+        LocalStorageStore.store(&settings_key(), &text);
+    }
+}
+
+pub fn permission_granted() -> bool {
+    Notification::permission() == NotificationPermission::Granted
+}
+
+/// Asks the browser for permission, reporting whether it was granted.
+pub fn request_permission(callback: Callback<bool>) {
+    match Notification::request_permission() {
+        Ok(promise) => wasm_bindgen_futures::spawn_local(async move {
+            let granted = JsFuture::from(promise)
+                .await
+                .ok()
+                .and_then(|permission| permission.as_string())
+                .map(|permission| permission == "granted")
+                .unwrap_or(false);
+            callback.emit(granted);
+        }),
+        Err(_) => callback.emit(false),
+    }
+}
+
+pub fn notify(tag: &str, title: &str, body: &str) {
+    let options = NotificationOptions::new();
+    options.set_body(body);
+    options.set_tag(tag);
+    if let Err(err) = Notification::new_with_options(title, &options) {
+        log::info!("notification failed: {:?}", err);
+    }
+}
+
+/// A short two-tone blast synthesized with Web Audio, so there is no
+/// sound file to ship.
+pub fn goal_horn() {
+    let play = || -> Result<(), wasm_bindgen::JsValue> {
+        let context = AudioContext::new()?;
+        let gain = context.create_gain()?;
+        gain.connect_with_audio_node(&context.destination())?;
+        let now = context.current_time();
+        gain.gain().set_value_at_time(0.2, now)?;
+        gain.gain().linear_ramp_to_value_at_time(0.0, now + 2.5)?;
+        for frequency in [155.0, 233.0] {
+            let oscillator = context.create_oscillator()?;
+            oscillator.set_type(OscillatorType::Sawtooth);
+            oscillator.frequency().set_value(frequency);
+            oscillator.connect_with_audio_node(&gain)?;
+            oscillator.start()?;
+            oscillator.stop_with_when(now + 2.5)?;
+        }
+        Ok(())
+    };
+    if let Err(err) = play() {
+        log::info!("goal horn failed: {:?}", err);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    franchise,
+    locale::{Label, Locale},
+    pages::{
+        games_page::{fetch_season_games, load_locale, questions_comments},
+        route_link, Route,
+    },
+    ratings::{trend_arrow, Ratings, INITIAL_RATING, TREND_GAMES},
+    season::Season,
+    Game,
+};
+use anyhow::Error;
+use chrono::Local;
+use yew::prelude::*;
+
+/// This season and the last, so early-season ratings aren't all 1500.
+const SEASONS: i32 = 2;
+
+/// Every game from `current` and the seasons before it that the ratings
+/// use, through the cache.
+pub(super) async fn rating_games(current: Season) -> Result<Vec<Game>, Error> {
+    let mut games = vec![];
+    for start_year in (current.start_year - SEASONS + 1)..=current.start_year {
+        let season = Season { start_year };
+        if season.cancelled() {
+            continue;
+        }
+        games.extend(fetch_season_games(season).await?);
+    }
+    Ok(games)
+}
+
+pub enum Msg {
+    FetchReady(Result<Vec<Game>, Error>),
+}
+
+/// Elo power ratings from this season's results and the last.
+pub struct RatingsPage {
+    ratings: Option<Ratings>,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl Component for RatingsPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let current = Season::for_date(Local::now().date_naive());
+            link.send_message(Msg::FetchReady(rating_games(current).await));
+        });
+        Self {
+            ratings: None,
+            error: None,
+            locale: load_locale(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::FetchReady(Ok(games)) => {
+                self.ratings = Some(Ratings::from_games(&games));
+                true
+            }
+            Msg::FetchReady(Err(err)) => {
+                log::info!("ratings schedule failed: {}", err);
+                self.error = Some(err.to_string());
+                true
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let back = html! {
+            { route_link(Route::Home, self.locale.text(Label::AllGames)) }
+        };
+        let ratings = match &self.ratings {
+            Some(ratings) => ratings,
+            None => {
+                return html! {
+                    <div class="container mt-4">
+                    { back }
+                    <h2>{ self.locale.text(if self.error.is_some() { Label::RatingsUnavailable } else { Label::Loading }) }</h2>
+                    { questions_comments(self.locale) }
+                    </div>
+                }
+            }
+        };
+        html! {
+            <div class="container mt-4">
+            { back }
+            <h1>{ self.locale.text(Label::PowerRatings) }</h1>
+            <table class="table table-sm w-auto">
+                <thead>
+                    <tr>
+                        <th></th>
+                        <th>{ self.locale.text(Label::Team) }</th>
+                        <th>{ self.locale.text(Label::Rating) }</th>
+                        <th>{ self.locale.last_games(TREND_GAMES) }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                {
+                    for ratings.table().iter().enumerate().map(|(rank, team)| html! {
+                        <tr>
+                            <td>{ rank + 1 }</td>
+                            <td>{ franchise::team_name(team.team_id).unwrap_or("?") }</td>
+                            <td>{ format!("{:.0}", team.rating) }</td>
+                            <td>{ format!("{:+.0} {}", team.trend, trend_arrow(team.trend)) }</td>
+                        </tr>
+                    })
+                }
+                </tbody>
+            </table>
+            <p class="text-muted">
+                { self.locale.ratings_note(
+                    ratings.settings.home_ice,
+                    ratings.settings.regression,
+                    INITIAL_RATING
+                ) }
+            </p>
+            { questions_comments(self.locale) }
+            </div>
+        }
+    }
+}
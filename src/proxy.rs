@@ -0,0 +1,179 @@
+use crate::{
+    cache::{schedule_ttl, state_ttl, Cache, CacheStore, KEY_PREFIX},
+    upstream::Upstream,
+    GameState, NextGameSchedule,
+};
+use anyhow::Error;
+use chrono::{Duration, Utc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Caches upstream API responses by game state and makes sure concurrent
+/// requests for the same path only reach the upstream once.
+pub struct Proxy<S> {
+    upstream: Arc<dyn Upstream>,
+    cache: Cache<S>,
+    game_states: Mutex<HashMap<usize, GameState>>,
+    in_flight: Mutex<HashMap<String, Arc<async_std::sync::Mutex<()>>>>,
+}
+
+impl<S: CacheStore> Proxy<S> {
+    pub fn new(upstream: Arc<dyn Upstream>, store: S) -> Self {
+        Self {
+            upstream,
+            cache: Cache::new(store),
+            game_states: Default::default(),
+            in_flight: Default::default(),
+        }
+    }
+
+    pub async fn get(&self, path: &str) -> Result<String, Error> {
+        let key = format!("{}:response:{}", KEY_PREFIX, path);
+        let lock = self
+            .in_flight
+            .lock()
+            .expect("in_flight")
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        let result = {
+            let _guard = lock.lock().await;
+            self.get_locked(&key, path).await
+        };
+        let mut in_flight = self.in_flight.lock().expect("in_flight");
+        if Arc::strong_count(&lock) == 2 {
+            in_flight.remove(&key);
+        }
+        result
+    }
+
+    async fn get_locked(&self, key: &str, path: &str) -> Result<String, Error> {
+        let cached = self.cache.load::<String>(key, Utc::now());
+        if let Some(cached) = &cached {
+            if cached.fresh {
+                return Ok(cached.value.clone());
+            }
+        }
+        match self.upstream.get(path).await {
+            Ok(text) => {
+                let ttl = self.ttl(path, &text);
+                self.cache.save(key, &text, ttl, Utc::now());
+                Ok(text)
+            }
+            Err(err) => match cached {
+                Some(cached) => {
+                    log::info!("serving stale {}: {}", path, err);
+                    Ok(cached.value)
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Schedules expire with their most volatile game. Other game
+    /// documents, like content, follow the state of their game as last
+    /// seen in a schedule.
+    fn ttl(&self, path: &str, text: &str) -> Option<Duration> {
+        if let Ok(schedule) = serde_json::from_str::<NextGameSchedule>(text) {
+            let mut game_states = self.game_states.lock().expect("game_states");
+            for game in schedule.dates.iter().flat_map(|date| date.games.iter()) {
+                game_states.insert(game.game_pk, game.state());
+            }
+            return schedule_ttl(&schedule);
+        }
+        let game_pk = game_pk_from_path(path);
+        let state = game_pk.and_then(|game_pk| {
+            self.game_states
+                .lock()
+                .expect("game_states")
+                .get(&game_pk)
+                .copied()
+        });
+        match state {
+            Some(state) => state_ttl(state),
+            None => Some(Duration::minutes(1)),
+        }
+    }
+}
+
+/// `api/v1/game/2022020275/content` -> `2022020275`
+pub fn game_pk_from_path(path: &str) -> Option<usize> {
+    let mut segments = path.trim_start_matches('/').split(&['/', '?'][..]);
+    segments.find(|segment| *segment == "game")?;
+    segments.next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cache::MemoryStore;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const SCHEDULE: &str = include_str!("../data/fixtures/schedule-2022-11-20.json");
+
+    #[derive(Default)]
+    struct CountingUpstream {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Upstream for CountingUpstream {
+        async fn get(&self, path: &str) -> Result<String, Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            async_std::task::sleep(std::time::Duration::from_millis(20)).await;
+            if path.contains("schedule") {
+                Ok(SCHEDULE.to_string())
+            } else {
+                Ok("{}".to_string())
+            }
+        }
+    }
+
+    #[test]
+    fn test_game_pk_from_path() {
+        assert_eq!(
+            game_pk_from_path("/api/v1/game/2022020275/content"),
+            Some(2022020275)
+        );
+        assert_eq!(game_pk_from_path("api/v1/schedule?date=2022-11-20"), None);
+    }
+
+    #[async_std::test]
+    async fn test_coalesces_concurrent_requests() {
+        let upstream = Arc::new(CountingUpstream::default());
+        let proxy = Arc::new(Proxy::new(upstream.clone(), MemoryStore::default()));
+        let requests: Vec<_> = (0..5)
+            .map(|_| {
+                let proxy = proxy.clone();
+                async_std::task::spawn(
+                    async move { proxy.get("api/v1/schedule?date=2022-11-20").await },
+                )
+            })
+            .collect();
+        for request in requests {
+            assert_eq!(request.await.unwrap(), SCHEDULE);
+        }
+        assert_eq!(upstream.calls.load(Ordering::SeqCst), 1);
+        assert!(proxy.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_content_follows_game_state() {
+        let upstream = Arc::new(CountingUpstream::default());
+        let proxy = Proxy::new(upstream.clone(), MemoryStore::default());
+        proxy.get("api/v1/schedule?date=2022-11-20").await.unwrap();
+
+        assert_eq!(proxy.ttl("api/v1/game/2022020275/content", "{}"), None);
+        assert_eq!(
+            proxy.ttl("api/v1/game/2022020277/content", "{}"),
+            Some(Duration::seconds(15))
+        );
+        assert_eq!(
+            proxy.ttl("api/v1/game/1/content", "{}"),
+            Some(Duration::minutes(1))
+        );
+    }
+}
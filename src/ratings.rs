@@ -0,0 +1,275 @@
+use crate::{franchise, head_to_head::season_of, season::Season, travel::local_date, Game};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// How many games a team's trend looks back over.
+pub const TREND_GAMES: usize = 10;
+
+/// How ratings move. The defaults follow the usual hockey Elo setup: a
+/// small K, since single games are noisy, and a third of the way back to
+/// the mean between seasons.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloSettings {
+    /// Most a rating moves on a one-goal game.
+    pub k: f64,
+    /// Added to the home team's rating when working out who should win.
+    pub home_ice: f64,
+    /// How much of the way back to the mean ratings go between seasons.
+    pub regression: f64,
+    /// What an overtime or shootout win counts as, since those are closer
+    /// to a coin flip than a regulation win.
+    pub overtime_win: f64,
+}
+
+impl Default for EloSettings {
+    fn default() -> Self {
+        Self {
+            k: 6.0,
+            home_ice: 50.0,
+            regression: 1.0 / 3.0,
+            overtime_win: 0.75,
+        }
+    }
+}
+
+impl EloSettings {
+    /// The chance a team rated `rating` beats one rated `other`, before
+    /// home ice.
+    pub fn expected(&self, rating: f64, other: f64) -> f64 {
+        1.0 / (1.0 + 10f64.powf((other - rating) / 400.0))
+    }
+
+    /// Bigger wins count for more, with diminishing returns.
+    pub fn margin_multiplier(&self, margin: usize) -> f64 {
+        0.6686 * (margin.max(1) as f64).ln() + 0.8048
+    }
+}
+
+/// A team's rating after one game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingPoint {
+    pub game_pk: usize,
+    pub date: NaiveDate,
+    pub opponent_id: usize,
+    pub rating: f64,
+    pub change: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamRating {
+    pub team_id: usize,
+    pub rating: f64,
+    /// The change over the last `TREND_GAMES` games.
+    pub trend: f64,
+    pub games: usize,
+}
+
+/// Ratings for every franchise, built up by replaying finished games.
+/// Relocated teams keep their franchise's rating.
+#[derive(Debug, Clone, Default)]
+pub struct Ratings {
+    pub settings: EloSettings,
+    ratings: HashMap<usize, f64>,
+    history: HashMap<usize, Vec<RatingPoint>>,
+    season: Option<Season>,
+}
+
+fn rated_team(team_id: usize) -> usize {
+    franchise::current_team_id(team_id).unwrap_or(team_id)
+}
+
+impl Ratings {
+    pub fn new(settings: EloSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    /// Replays every finished regular season and playoff game in order.
+    pub fn from_games<'a>(games: impl IntoIterator<Item = &'a Game>) -> Self {
+        let mut ratings = Self::default();
+        ratings.replay(games);
+        ratings
+    }
+
+    pub fn replay<'a>(&mut self, games: impl IntoIterator<Item = &'a Game>) {
+        let mut games: Vec<&Game> = games
+            .into_iter()
+            .filter(|game| game.is_finished() && (game.is_regular_season() || game.is_playoffs()))
+            .collect();
+        games.sort_by_key(|game| (game.game_date, game.game_pk));
+        games.dedup_by_key(|game| game.game_pk);
+        for game in games {
+            self.apply(game);
+        }
+    }
+
+    pub fn rating(&self, team_id: usize) -> f64 {
+        self.ratings
+            .get(&rated_team(team_id))
+            .copied()
+            .unwrap_or(INITIAL_RATING)
+    }
+
+    /// The chance the home team wins, with home ice.
+    pub fn home_win_probability(&self, home_id: usize, away_id: usize) -> f64 {
+        self.settings.expected(
+            self.rating(home_id) + self.settings.home_ice,
+            self.rating(away_id),
+        )
+    }
+
+    pub fn history(&self, team_id: usize) -> &[RatingPoint] {
+        self.history
+            .get(&rated_team(team_id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Pulls every rating part of the way back to the mean.
+    fn regress(&mut self) {
+        let regression = self.settings.regression;
+        for rating in self.ratings.values_mut() {
+            *rating += (INITIAL_RATING - *rating) * regression;
+        }
+    }
+
+    fn apply(&mut self, game: &Game) {
+        let season = season_of(game);
+        if self.season.is_some_and(|current| current != season) {
+            self.regress();
+        }
+        self.season = Some(season);
+
+        let (home, away) = (&game.teams.home, &game.teams.away);
+        let home_id = rated_team(home.team.id);
+        let away_id = rated_team(away.team.id);
+        let expected = self.home_win_probability(home_id, away_id);
+        let home_won = home.score > away.score;
+        let won = if game.went_to_overtime() {
+            self.settings.overtime_win
+        } else {
+            1.0
+        };
+        let actual = if home_won { won } else { 1.0 - won };
+        let margin = home.score.abs_diff(away.score);
+        let change =
+            self.settings.k * self.settings.margin_multiplier(margin) * (actual - expected);
+
+        let date = local_date(game);
+        for (team_id, opponent_id, change) in
+            [(home_id, away_id, change), (away_id, home_id, -change)]
+        {
+            let rating = self.ratings.entry(team_id).or_insert(INITIAL_RATING);
+            *rating += change;
+            let point = RatingPoint {
+                game_pk: game.game_pk,
+                date,
+                opponent_id,
+                rating: *rating,
+                change,
+            };
+            self.history.entry(team_id).or_default().push(point);
+        }
+    }
+
+    /// Every rated team, best first.
+    pub fn table(&self) -> Vec<TeamRating> {
+        let mut table: Vec<TeamRating> = self
+            .ratings
+            .iter()
+            .map(|(team_id, rating)| {
+                let history = self.history(*team_id);
+                let recent = &history[history.len().saturating_sub(TREND_GAMES)..];
+                TeamRating {
+                    team_id: *team_id,
+                    rating: *rating,
+                    trend: recent.iter().map(|point| point.change).sum(),
+                    games: history.len(),
+                }
+            })
+            .collect();
+        table.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        table
+    }
+
+    /// One line per team, for the command line.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (rank, team) in self.table().iter().enumerate() {
+            text.push_str(&format!(
+                "{:>2}. {:<3} {:>5.0} {:>+4.0} {}\n",
+                rank + 1,
+                franchise::abbreviation(team.team_id).unwrap_or("?"),
+                team.rating,
+                team.trend,
+                trend_arrow(team.trend)
+            ));
+        }
+        text
+    }
+}
+
+/// ▲ or ▼ for trends of at least a point, otherwise –.
+pub fn trend_arrow(trend: f64) -> &'static str {
+    if trend >= 1.0 {
+        "▲"
+    } else if trend <= -1.0 {
+        "▼"
+    } else {
+        "–"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{teams, NextGameSchedule};
+    use serde_json::Value;
+
+    #[test]
+    fn test_ratings() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let games = &schedule.dates[0].games;
+        let ratings = Ratings::from_games(games);
+        // Two finished games: Sharks over the Oilers in overtime and the
+        // Canadiens over the Bruins.
+        let sharks = ratings.rating(teams::SAN_JOSE_SHARKS_ID);
+        let oilers = ratings.rating(teams::EDMONTON_OILERS_ID);
+        assert!(sharks > INITIAL_RATING);
+        assert!((sharks - INITIAL_RATING + oilers - INITIAL_RATING).abs() < 1e-9);
+        let table = ratings.table();
+        assert_eq!(table.len(), 4);
+        let sharks_row = table
+            .iter()
+            .find(|team| team.team_id == teams::SAN_JOSE_SHARKS_ID)
+            .unwrap();
+        assert!((sharks_row.trend - (sharks - INITIAL_RATING)).abs() < 1e-9);
+        assert_eq!(ratings.history(teams::SAN_JOSE_SHARKS_ID).len(), 1);
+
+        // The home team winning in overtime moves less than a regulation
+        // win by the same margin.
+        let mut regulation: Value = serde_json::to_value(&games[0]).unwrap();
+        regulation["linescore"]["currentPeriod"] = Value::from(3);
+        let regulation: Game = serde_json::from_value(regulation).unwrap();
+        let regulation_ratings = Ratings::from_games([&regulation]);
+        assert!(regulation_ratings.rating(teams::SAN_JOSE_SHARKS_ID) > sharks);
+
+        // A new season pulls ratings back toward the mean.
+        let mut next_season: Value = serde_json::to_value(&games[1]).unwrap();
+        next_season["season"] = Value::from("20232024");
+        next_season["gameDate"] = Value::from("2023-10-12T23:00:00Z");
+        next_season["gamePk"] = Value::from(2023020001);
+        let next_season: Game = serde_json::from_value(next_season).unwrap();
+        let mut replayed = Ratings::default();
+        replayed.replay([&games[0]]);
+        replayed.replay([&next_season]);
+        let regressed = INITIAL_RATING + (sharks - INITIAL_RATING) * 2.0 / 3.0;
+        assert!((replayed.rating(teams::SAN_JOSE_SHARKS_ID) - regressed).abs() < 1e-9);
+    }
+}
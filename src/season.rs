@@ -0,0 +1,105 @@
+use chrono::{Datelike, NaiveDate};
+
+/// A league season, named by the calendar year it starts in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Season {
+    pub start_year: i32,
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("date")
+}
+
+/// The first day that counts toward the season starting in `year`.
+/// Usually July 1, after the draft, but the 2020 playoffs ran into
+/// September and the 2021 Final into July.
+fn cutover(year: i32) -> NaiveDate {
+    match year {
+        2020 => ymd(2020, 10, 1),
+        2021 => ymd(2021, 7, 15),
+        _ => ymd(year, 7, 1),
+    }
+}
+
+impl Season {
+    pub fn for_date(date: NaiveDate) -> Self {
+        let year = date.year();
+        let start_year = if date >= cutover(year) {
+            year
+        } else {
+            year - 1
+        };
+        Self { start_year }
+    }
+
+    /// Parses an API season id like `20222023`.
+    pub fn from_id(id: &str) -> Option<Self> {
+        if id.len() != 8 {
+            return None;
+        }
+        let start_year: i32 = id[..4].parse().ok()?;
+        let end_year: i32 = id[4..].parse().ok()?;
+        if end_year != start_year + 1 {
+            return None;
+        }
+        Some(Self { start_year })
+    }
+
+    /// `20222023`, as the API writes it.
+    pub fn id(&self) -> String {
+        format!("{}{}", self.start_year, self.start_year + 1)
+    }
+
+    /// `2022-23`
+    pub fn label(&self) -> String {
+        format!("{}-{:02}", self.start_year, (self.start_year + 1) % 100)
+    }
+
+    /// The 2004-05 season was lost to a lockout.
+    pub fn cancelled(&self) -> bool {
+        self.start_year == 2004
+    }
+
+    /// Roughly when games begin: preseason in the fall, except after the
+    /// 1994, 2012 and 2020 delays.
+    pub fn first_game_date(&self) -> NaiveDate {
+        match self.start_year {
+            1994 => ymd(1995, 1, 20),
+            2012 => ymd(2013, 1, 19),
+            2020 => ymd(2021, 1, 13),
+            year => ymd(year, 9, 15),
+        }
+    }
+
+    /// Between the end of one season's playoffs and the next season's
+    /// first games.
+    pub fn is_offseason(date: NaiveDate) -> bool {
+        let season = Self::for_date(date);
+        season.cancelled() || date < season.first_game_date()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_season_boundaries() {
+        assert_eq!(Season::for_date(ymd(2022, 11, 20)).id(), "20222023");
+        assert_eq!(Season::for_date(ymd(2023, 6, 13)).label(), "2022-23");
+        assert_eq!(Season::for_date(ymd(2023, 7, 1)).label(), "2023-24");
+        // The bubble playoffs and the delayed 2021 Final.
+        assert_eq!(Season::for_date(ymd(2020, 9, 28)).id(), "20192020");
+        assert_eq!(Season::for_date(ymd(2021, 7, 7)).id(), "20202021");
+        assert_eq!(
+            Season::from_id("19992000"),
+            Some(Season { start_year: 1999 })
+        );
+        assert_eq!(Season::from_id("20222022"), None);
+
+        assert!(Season::is_offseason(ymd(2022, 8, 1)));
+        assert!(!Season::is_offseason(ymd(2022, 11, 20)));
+        assert!(Season::is_offseason(ymd(2004, 12, 1)));
+        assert!(Season::is_offseason(ymd(2020, 12, 1)));
+    }
+}
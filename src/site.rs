@@ -0,0 +1,319 @@
+use crate::{
+    summary::{Layout, SummaryFormatter, TextFormatter},
+    teams::TeamInfo,
+    Game, GameGroups,
+};
+use chrono::{NaiveDate, Offset, TimeZone};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+const STYLESHEETS: &str = r#"<link href="https://cdn.jsdelivr.net/npm/bootstrap@5.2.2/dist/css/bootstrap.min.css" rel="stylesheet" integrity="sha384-Zenh87qX5JnK2Jl0vWa8Ck2rdkQ2Bzep5IDxbcnCeuOxjzrPF/et3URy9Bv1WTRi" crossorigin="anonymous">
+<link href="/styles.css" rel="stylesheet">"#;
+
+/// One generated file, with `path` relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitePage {
+    pub path: String,
+    pub contents: String,
+}
+
+/// The games for one date, as returned by the schedule endpoint.
+#[derive(Debug, Clone)]
+pub struct SiteDay {
+    pub date: NaiveDate,
+    pub games: Vec<Game>,
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+pub fn date_path(date: NaiveDate) -> String {
+    format!("{}.html", date.format("%F"))
+}
+
+pub fn team_path(team: &TeamInfo) -> String {
+    format!("teams/{}.html", team.abbreviation.to_lowercase())
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         {}\n<link rel=\"alternate\" type=\"application/rss+xml\" title=\"NHL Games Today\" href=\"/feed.xml\">\n\
+         <title>{}</title>\n</head>\n<body>\n<div class=\"container mt-4\">\n{}</div>\n</body>\n</html>\n",
+        STYLESHEETS,
+        escape(title),
+        body
+    )
+}
+
+/// Seconds west of UTC in `zone` when the game starts, as
+/// `Game::summary` takes them, so daylight time is followed.
+fn offset(zone: Tz, game: &Game) -> f64 {
+    let offset = zone.offset_from_utc_datetime(&game.game_date.naive_utc());
+    -offset.fix().local_minus_utc() as f64
+}
+
+fn game_item(game: &Game, text: &str) -> String {
+    let class = game.class();
+    if class.is_empty() {
+        format!("<li>{}</li>\n", escape(text))
+    } else {
+        format!("<li class=\"{}\">{}</li>\n", class, escape(text))
+    }
+}
+
+/// Renders a day the same way `GamesToday::view` does: one section per
+/// game state, with broadcasters listed for games that haven't finished.
+pub fn date_page(
+    day: &SiteDay,
+    previews: &HashMap<usize, String>,
+    zone: Tz,
+    formatter: &TextFormatter,
+    previous: Option<NaiveDate>,
+    next: Option<NaiveDate>,
+) -> String {
+    let groups = GameGroups::new(&day.games);
+    let mut body = format!(
+        "<h1>{}: {}</h1>\n",
+        formatter.locale.long_date(day.date),
+        formatter.locale.games_count(day.games.len())
+    );
+    for (title, games) in groups.sections_in(formatter.locale).iter() {
+        if games.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("<div>\n<h2>{}</h2>\n<ul>\n", title));
+        for game in games.iter() {
+            let mut summary = game.summary(offset(zone, game));
+            // Live and upcoming games list their broadcasters.
+            if game.is_live() || game.is_preview() {
+                if let Some(preview) = previews.get(&game.game_pk) {
+                    summary = summary.with_preview(preview);
+                }
+            }
+            let text = formatter.format(&summary);
+            body.push_str(&game_item(game, &text));
+        }
+        body.push_str("</ul>\n</div>\n");
+    }
+    body.push_str("<nav>\n");
+    if let Some(previous) = previous {
+        body.push_str(&format!(
+            "<a href=\"/{}\">&larr; {}</a>\n",
+            date_path(previous),
+            previous.format("%F")
+        ));
+    }
+    body.push_str("<a href=\"/index.html\">All dates</a>\n");
+    if let Some(next) = next {
+        body.push_str(&format!(
+            "<a href=\"/{}\">{} &rarr;</a>\n",
+            date_path(next),
+            next.format("%F")
+        ));
+    }
+    body.push_str("</nav>\n");
+    document(&format!("NHL Games {}", day.date.format("%F")), &body)
+}
+
+pub fn team_page(team: &TeamInfo, days: &[SiteDay], zone: Tz, formatter: &TextFormatter) -> String {
+    let mut body = format!("<h1>{}</h1>\n<ul>\n", escape(&team.name));
+    for day in days {
+        for game in day.games.iter().filter(|game| game.has_competitor(team.id)) {
+            let summary = game.summary(offset(zone, game));
+            let text = if game.is_finished() {
+                let scoreboard = TextFormatter {
+                    layout: Layout::Scoreboard,
+                    ..*formatter
+                };
+                format!("{} {}", day.date.format("%F"), scoreboard.format(&summary))
+            } else {
+                format!("{} {}", day.date.format("%F"), formatter.format(&summary))
+            };
+            body.push_str(&format!(
+                "<li><a href=\"/{}\">{}</a></li>\n",
+                date_path(day.date),
+                escape(&text)
+            ));
+        }
+    }
+    body.push_str("</ul>\n<a href=\"/index.html\">All dates</a>\n");
+    document(&team.name, &body)
+}
+
+pub fn index_page(days: &[SiteDay], teams: &[&TeamInfo]) -> String {
+    let mut body = String::from("<h1>NHL Games Today</h1>\n<h2>Dates</h2>\n<ul>\n");
+    for day in days {
+        body.push_str(&format!(
+            "<li><a href=\"/{}\">{}</a> ({} games)</li>\n",
+            date_path(day.date),
+            day.date.format("%A, %B %-d, %Y"),
+            day.games.len()
+        ));
+    }
+    body.push_str("</ul>\n<h2>Teams</h2>\n<ul>\n");
+    for team in teams {
+        body.push_str(&format!(
+            "<li><a href=\"/{}\">{}</a></li>\n",
+            team_path(team),
+            escape(&team.name)
+        ));
+    }
+    body.push_str("</ul>\n");
+    document("NHL Games Today", &body)
+}
+
+/// An RSS feed with one item per date, for feed readers.
+pub fn feed(days: &[SiteDay], base_url: &str, zone: Tz, formatter: &TextFormatter) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let mut items = String::new();
+    for day in days.iter().rev() {
+        let description: Vec<String> = day
+            .games
+            .iter()
+            .map(|game| formatter.format(&game.summary(offset(zone, game))))
+            .collect();
+        items.push_str(&format!(
+            "<item><title>NHL games {date}</title><link>{base}/{path}</link><guid>{base}/{path}</guid><description>{description}</description></item>\n",
+            date = day.date.format("%F"),
+            base = escape(base_url),
+            path = date_path(day.date),
+            description = escape(&description.join("\n")),
+        ));
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\"><channel>\n\
+         <title>NHL Games Today</title><link>{}/index.html</link><description>NHL games by date</description>\n{}</channel></rss>\n",
+        escape(base_url),
+        items
+    )
+}
+
+/// Builds every page for `days`, which should be sorted by date.
+pub fn build(
+    days: &[SiteDay],
+    teams: &[&TeamInfo],
+    previews: &HashMap<usize, String>,
+    zone: Tz,
+    formatter: &TextFormatter,
+    base_url: &str,
+) -> Vec<SitePage> {
+    let mut pages = vec![
+        SitePage {
+            path: "index.html".to_string(),
+            contents: index_page(days, teams),
+        },
+        SitePage {
+            path: "feed.xml".to_string(),
+            contents: feed(days, base_url, zone, formatter),
+        },
+    ];
+    for (index, day) in days.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| days[index].date);
+        let next = days.get(index + 1).map(|day| day.date);
+        pages.push(SitePage {
+            path: date_path(day.date),
+            contents: date_page(day, previews, zone, formatter, previous, next),
+        });
+    }
+    for team in teams {
+        pages.push(SitePage {
+            path: team_path(team),
+            contents: team_page(team, days, zone, formatter),
+        });
+    }
+    pages
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{locale::Locale, teams, NextGameSchedule};
+
+    fn day() -> SiteDay {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        SiteDay {
+            date: NaiveDate::from_ymd_opt(2022, 11, 20).unwrap(),
+            games: schedule.dates[0].games.clone(),
+        }
+    }
+
+    #[test]
+    fn test_date_page() {
+        let mut previews = HashMap::new();
+        previews.insert(2022020279, "ESPN+".to_string());
+        let finished = day().games.into_iter().find(|game| game.is_finished()).unwrap();
+        previews.insert(finished.game_pk, "TNT".to_string());
+        let html = date_page(
+            &day(),
+            &previews,
+            chrono_tz::America::Los_Angeles,
+            &TextFormatter::default(),
+            None,
+            None,
+        );
+
+        assert!(html.contains("<h1>Sunday, November 20, 2022: 7 games</h1>"));
+        let live = html.find("<h2>Live</h2>").unwrap();
+        let upcoming = html.find("<h2>Upcoming</h2>").unwrap();
+        let finished = html.find("<h2>Finished</h2>").unwrap();
+        let postponed = html.find("<h2>Postponed</h2>").unwrap();
+        assert!(live < upcoming && upcoming < finished && finished < postponed);
+        assert!(html.contains("<li class=\"oilers-sharks\">Edmonton Oilers @ San Jose Sharks</li>"));
+        assert!(html
+            .contains("<li>\u{a0}7:00 PM Detroit Red Wings @ Vegas Golden Knights (ESPN+)</li>"));
+        assert!(html.contains("Montréal Canadiens"));
+        assert!(!html.contains("TNT"));
+
+        let french = date_page(
+            &day(),
+            &previews,
+            chrono_tz::America::New_York,
+            &TextFormatter::default().localized(Locale::French),
+            None,
+            None,
+        );
+        assert!(french.contains("<h1>dimanche 20 novembre 2022: 7 matchs</h1>"));
+        assert!(french.contains("<h2>En cours</h2>"));
+        assert!(french.contains("Los Angeles Kings @ Seattle Kraken 1re 12:34 ENT"));
+        assert!(french.contains("22 h 00 Detroit Red Wings @ Vegas Golden Knights (ESPN+)"));
+    }
+
+    #[test]
+    fn test_build() {
+        let days = vec![day()];
+        let sharks = teams::team_info(teams::SAN_JOSE_SHARKS_ID).unwrap();
+        let pages = build(
+            &days,
+            &[sharks],
+            &HashMap::new(),
+            chrono_tz::UTC,
+            &TextFormatter::default(),
+            "https://example.com/",
+        );
+        let paths: Vec<_> = pages.iter().map(|page| page.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "index.html",
+                "feed.xml",
+                "2022-11-20.html",
+                "teams/sjs.html"
+            ]
+        );
+        assert!(pages[3]
+            .contents
+            .contains("2022-11-20 Edmonton Oilers 3 @ San Jose Sharks 4 Final (OT)"));
+        assert!(pages[1]
+            .contents
+            .contains("<link>https://example.com/2022-11-20.html</link>"));
+    }
+}
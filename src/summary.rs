@@ -0,0 +1,389 @@
+use crate::{
+    franchise,
+    locale::{Label, Locale},
+    teams, Game, GameState, TeamAtGame,
+};
+use anyhow::{bail, Error};
+use chrono::{DateTime, FixedOffset};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryTeam {
+    pub id: usize,
+    pub name: String,
+    pub abbreviation: Option<&'static str>,
+    pub nickname: Option<&'static str>,
+    pub score: usize,
+}
+
+impl From<&TeamAtGame> for SummaryTeam {
+    fn from(team: &TeamAtGame) -> Self {
+        Self {
+            id: team.team.id,
+            name: team.team.display_name().to_string(),
+            abbreviation: franchise::abbreviation(team.team.id),
+            nickname: teams::TEAM_NICKNAMES.get(&team.team.id).copied(),
+            score: team.score,
+        }
+    }
+}
+
+/// Where a game stands, with what the API says about the clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Clock {
+    Pregame,
+    TimeTbd,
+    Running {
+        period_ordinal: String,
+        time_remaining: String,
+    },
+    Intermission {
+        period_ordinal: String,
+        seconds_remaining: usize,
+    },
+    Final {
+        overtime: bool,
+    },
+    Postponed,
+}
+
+/// Everything a renderer needs to show a game, with no formatting applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSummary {
+    pub game_pk: usize,
+    pub state: GameState,
+    pub away: SummaryTeam,
+    pub home: SummaryTeam,
+    pub clock: Clock,
+    /// The scheduled start in the viewer's time zone.
+    pub start: DateTime<FixedOffset>,
+    pub broadcasters: Vec<String>,
+    pub game_type: Option<Label>,
+}
+
+impl GameSummary {
+    /// `offset` is seconds west of UTC, as `Game::describe` takes it.
+    pub fn new(game: &Game, offset: f64) -> Self {
+        let linescore = &game.linescore;
+        let clock = if game.is_finished() {
+            Clock::Final {
+                overtime: game.went_to_overtime(),
+            }
+        } else if game.is_live() {
+            if linescore.intermission_info.in_intermission {
+                Clock::Intermission {
+                    period_ordinal: linescore.current_period_ordinal.clone(),
+                    seconds_remaining: linescore.intermission_info.intermission_time_remaining,
+                }
+            } else {
+                Clock::Running {
+                    period_ordinal: linescore.current_period_ordinal.clone(),
+                    time_remaining: linescore.current_period_time_remaining.clone(),
+                }
+            }
+        } else if game.is_tbd() {
+            Clock::TimeTbd
+        } else if game.is_postponed() {
+            Clock::Postponed
+        } else {
+            Clock::Pregame
+        };
+        let tz = FixedOffset::west_opt(offset as i32).unwrap();
+        Self {
+            game_pk: game.game_pk,
+            state: game.state(),
+            away: (&game.teams.away).into(),
+            home: (&game.teams.home).into(),
+            clock,
+            start: game.game_date.with_timezone(&tz),
+            broadcasters: vec![],
+            game_type: game.game_type_label(),
+        }
+    }
+
+    /// Adds the broadcasters from a preview string like "NBCSCA, ESPN+".
+    pub fn with_preview(mut self, preview: &str) -> Self {
+        self.broadcasters = preview
+            .split(',')
+            .map(str::trim)
+            .filter(|broadcaster| !broadcaster.is_empty())
+            .map(str::to_string)
+            .collect();
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeFormat {
+    /// " 7:00 PM", padded so times line up.
+    #[default]
+    TwelveHour,
+    /// "19:00"
+    TwentyFourHour,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TeamNames {
+    /// "San Jose Sharks"
+    #[default]
+    Full,
+    /// "SJS"
+    Abbreviation,
+    /// "Sharks", from `TEAM_NICKNAMES`
+    Nickname,
+}
+
+impl FromStr for TeamNames {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Error> {
+        match text {
+            "full" => Ok(TeamNames::Full),
+            "abbreviation" => Ok(TeamNames::Abbreviation),
+            "nickname" => Ok(TeamNames::Nickname),
+            _ => bail!(
+                "unknown team names '{}', expected full, abbreviation or nickname",
+                text
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// The start time before a game, the clock during it and just the
+    /// teams after it, as on the main page.
+    #[default]
+    Standard,
+    /// The date and start time, for lists that span days.
+    Upcoming,
+    /// "Away @ Home"
+    TeamsOnly,
+    /// Scores once the game starts.
+    Scoreboard,
+}
+
+/// Turns a summary into text. Renderers that want markup can implement
+/// this themselves.
+pub trait SummaryFormatter {
+    fn format(&self, summary: &GameSummary) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextFormatter {
+    pub time: TimeFormat,
+    pub names: TeamNames,
+    pub layout: Layout,
+    pub locale: Locale,
+}
+
+impl TextFormatter {
+    pub fn new(layout: Layout) -> Self {
+        Self {
+            layout,
+            ..Default::default()
+        }
+    }
+
+    pub fn time_format(self, time: TimeFormat) -> Self {
+        Self { time, ..self }
+    }
+
+    pub fn team_names(self, names: TeamNames) -> Self {
+        Self { names, ..self }
+    }
+
+    /// Switches the labels and dates to `locale`, and the clock to the one
+    /// it usually uses.
+    pub fn localized(self, locale: Locale) -> Self {
+        let time = if locale.twenty_four_hour() {
+            TimeFormat::TwentyFourHour
+        } else {
+            TimeFormat::TwelveHour
+        };
+        Self {
+            locale,
+            time,
+            ..self
+        }
+    }
+
+    pub fn team<'a>(&self, team: &'a SummaryTeam) -> &'a str {
+        match self.names {
+            TeamNames::Full => &team.name,
+            TeamNames::Abbreviation => team.abbreviation.unwrap_or(&team.name),
+            TeamNames::Nickname => team.nickname.unwrap_or(&team.name),
+        }
+    }
+
+    pub fn start_time(&self, start: &DateTime<FixedOffset>) -> String {
+        self.locale
+            .time(start, self.time == TimeFormat::TwentyFourHour)
+    }
+
+    fn matchup(&self, summary: &GameSummary) -> String {
+        format!(
+            "{} @ {}",
+            self.team(&summary.away),
+            self.team(&summary.home)
+        )
+    }
+
+    fn scores(&self, summary: &GameSummary) -> String {
+        format!(
+            "{} {} @ {} {}",
+            self.team(&summary.away),
+            summary.away.score,
+            self.team(&summary.home),
+            summary.home.score
+        )
+    }
+
+    fn clock(&self, clock: &Clock) -> Option<String> {
+        match clock {
+            Clock::Running {
+                period_ordinal,
+                time_remaining,
+            } => Some(format!(
+                "{} {}",
+                self.locale.period_ordinal(period_ordinal),
+                time_remaining
+            )),
+            Clock::Intermission {
+                period_ordinal,
+                seconds_remaining,
+            } => Some(format!(
+                "{} {}:{:02} {}",
+                self.locale.period_ordinal(period_ordinal),
+                seconds_remaining / 60,
+                seconds_remaining % 60,
+                self.locale.text(Label::Intermission)
+            )),
+            _ => None,
+        }
+    }
+
+    fn body(&self, summary: &GameSummary) -> String {
+        match self.layout {
+            Layout::TeamsOnly => self.matchup(summary),
+            Layout::Upcoming => format!(
+                "{} {} {}",
+                self.locale.short_date(summary.start.date_naive()),
+                self.start_time(&summary.start),
+                self.matchup(summary)
+            ),
+            Layout::Standard => match &summary.clock {
+                Clock::Final { .. } | Clock::TimeTbd => self.matchup(summary),
+                Clock::Pregame | Clock::Postponed => {
+                    format!(
+                        "{} {}",
+                        self.start_time(&summary.start),
+                        self.matchup(summary)
+                    )
+                }
+                clock => format!(
+                    "{} {}",
+                    self.matchup(summary),
+                    self.clock(clock).unwrap_or_default()
+                ),
+            },
+            Layout::Scoreboard => match &summary.clock {
+                Clock::Final { overtime } => {
+                    let mut text = format!(
+                        "{} {}",
+                        self.scores(summary),
+                        self.locale.text(Label::Final)
+                    );
+                    if *overtime {
+                        text.push_str(&format!(" ({})", self.locale.text(Label::Overtime)));
+                    }
+                    text
+                }
+                Clock::Pregame => {
+                    format!(
+                        "{} {}",
+                        self.start_time(&summary.start),
+                        self.matchup(summary)
+                    )
+                }
+                Clock::TimeTbd => format!(
+                    "{} {}",
+                    self.matchup(summary),
+                    self.locale.text(Label::TimeTbd)
+                ),
+                Clock::Postponed => format!(
+                    "{} {}",
+                    self.matchup(summary),
+                    self.locale.text(Label::Postponed)
+                ),
+                clock => format!(
+                    "{} {}",
+                    self.scores(summary),
+                    self.clock(clock).unwrap_or_default()
+                ),
+            },
+        }
+    }
+}
+
+impl SummaryFormatter for TextFormatter {
+    /// Broadcasters, when the summary has them, follow in parentheses.
+    fn format(&self, summary: &GameSummary) -> String {
+        let body = self.body(summary);
+        if summary.broadcasters.is_empty() || self.layout == Layout::TeamsOnly {
+            body
+        } else {
+            format!("{} ({})", body, summary.broadcasters.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+
+    #[test]
+    fn test_formatters() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let games = &schedule.dates[0].games;
+        let pacific = 8.0 * 3600.0;
+        let upcoming = games.iter().find(|game| game.is_preview()).unwrap();
+        let finished = games.iter().find(|game| game.is_finished()).unwrap();
+
+        let described: Vec<String> = games.iter().map(|game| game.describe(pacific)).collect();
+        assert_eq!(
+            described,
+            [
+                "Edmonton Oilers @ San Jose Sharks",
+                "Boston Bruins @ Montréal Canadiens",
+                "Toronto Maple Leafs @ Dallas Stars 2nd 07:42",
+                "Los Angeles Kings @ Seattle Kraken 1st 12:34 INT",
+                "\u{a0}7:00 PM Detroit Red Wings @ Vegas Golden Knights",
+                "New York Rangers @ Vancouver Canucks",
+                "\u{a0}4:00 PM Chicago Blackhawks @ Winnipeg Jets",
+            ]
+        );
+
+        let tbd = games.iter().find(|game| game.is_tbd()).unwrap();
+        assert_eq!(GameSummary::new(tbd, pacific).clock, Clock::TimeTbd);
+
+        let summary = GameSummary::new(upcoming, pacific).with_preview("ESPN+, NHLN");
+        let formatter = TextFormatter::default()
+            .time_format(TimeFormat::TwentyFourHour)
+            .team_names(TeamNames::Abbreviation);
+        assert_eq!(formatter.format(&summary), "19:00 DET @ VGK (ESPN+, NHLN)");
+
+        let scoreboard = TextFormatter::new(Layout::Scoreboard).team_names(TeamNames::Nickname);
+        assert_eq!(
+            scoreboard.format(&GameSummary::new(finished, pacific)),
+            "Oilers 3 @ Sharks 4 Final (OT)"
+        );
+        assert_eq!(
+            "nickname".parse::<TeamNames>().unwrap(),
+            TeamNames::Nickname
+        );
+    }
+}
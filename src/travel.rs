@@ -0,0 +1,373 @@
+use crate::{franchise, locale::Locale, teams, Game};
+use chrono::{DateTime, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Arena coordinates for the venues in `data/teams.json`, which only has
+/// names and cities.
+#[rustfmt::skip]
+const VENUE_COORDINATES: &[(usize, f64, f64)] = &[
+    (teams::NEW_JERSEY_DEVILS_ID, 40.7336, -74.1711),
+    (teams::NEW_YORK_ISLANDERS_ID, 40.7126, -73.7270),
+    (teams::NEW_YORK_RANGERS_ID, 40.7505, -73.9934),
+    (teams::PHILADELPHIA_FLYERS_ID, 39.9012, -75.1720),
+    (teams::PITTSBURGH_PENGUINS_ID, 40.4394, -79.9892),
+    (teams::BOSTON_BRUINS_ID, 42.3662, -71.0621),
+    (teams::BUFFALO_SABRES_ID, 42.8750, -78.8764),
+    (teams::MONTREAL_CANADIENS_ID, 45.4961, -73.5693),
+    (teams::OTTAWA_SENATORS_ID, 45.2969, -75.9272),
+    (teams::TORONTO_MAPLE_LEAFS_ID, 43.6435, -79.3791),
+    (teams::CAROLINA_HURRICANES_ID, 35.8033, -78.7219),
+    (teams::FLORIDA_PANTHERS_ID, 26.1584, -80.3256),
+    (teams::TAMPA_BAY_LIGHTNING_ID, 27.9428, -82.4519),
+    (teams::WASHINGTON_CAPITALS_ID, 38.8981, -77.0209),
+    (teams::CHICAGO_BLACKHAWKS_ID, 41.8807, -87.6742),
+    (teams::DETROIT_RED_WINGS_ID, 42.3411, -83.0553),
+    (teams::NASHVILLE_PREDATORS_ID, 36.1592, -86.7785),
+    (teams::ST_LOUIS_BLUES_ID, 38.6268, -90.2026),
+    (teams::CALGARY_FLAMES_ID, 51.0374, -114.0519),
+    (teams::COLORADO_AVALANCHE_ID, 39.7487, -105.0077),
+    (teams::EDMONTON_OILERS_ID, 53.5469, -113.4978),
+    (teams::VANCOUVER_CANUCKS_ID, 49.2778, -123.1089),
+    (teams::ANAHEIM_DUCKS_ID, 33.8078, -117.8765),
+    (teams::DALLAS_STARS_ID, 32.7905, -96.8103),
+    (teams::LOS_ANGELES_KINGS_ID, 34.0430, -118.2673),
+    (teams::SAN_JOSE_SHARKS_ID, 37.3328, -121.9012),
+    (teams::COLUMBUS_BLUE_JACKETS_ID, 39.9692, -83.0061),
+    (teams::MINNESOTA_WILD_ID, 44.9448, -93.1010),
+    (teams::WINNIPEG_JETS_ID, 49.8928, -97.1436),
+    (teams::ARIZONA_COYOTES_ID, 33.5319, -112.2611),
+    (teams::VEGAS_GOLDEN_KNIGHTS_ID, 36.1029, -115.1784),
+    (teams::SEATTLE_KRAKEN_ID, 47.6221, -122.3540),
+];
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Used for the local date of games in arenas we don't know.
+const DEFAULT_ZONE: Tz = chrono_tz::America::New_York;
+
+/// Latitude and longitude of a team's home arena.
+pub fn venue_coordinates(team_id: usize) -> Option<(f64, f64)> {
+    VENUE_COORDINATES
+        .iter()
+        .find(|(id, _, _)| *id == team_id)
+        .map(|(_, latitude, longitude)| (*latitude, *longitude))
+}
+
+/// Great-circle distance between two teams' arenas.
+pub fn distance_km(team_id: usize, other_team_id: usize) -> Option<f64> {
+    let (lat1, lon1) = venue_coordinates(team_id)?;
+    let (lat2, lon2) = venue_coordinates(other_team_id)?;
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    Some(2.0 * EARTH_RADIUS_KM * a.sqrt().asin())
+}
+
+/// The arena's time zone, from its id in `data/teams.json`.
+fn venue_zone(team_id: usize) -> Option<Tz> {
+    teams::team_info(team_id)?.venue.time_zone.id.parse().ok()
+}
+
+/// Hours from UTC at the arena at that moment, daylight saving included.
+fn utc_offset(team_id: usize, at: DateTime<Utc>) -> Option<i32> {
+    let offset = venue_zone(team_id)?.offset_from_utc_datetime(&at.naive_utc());
+    Some(offset.fix().local_minus_utc() / 3600)
+}
+
+/// Hours of time zone change between two arenas at that moment, so
+/// Arizona is an hour nearer the Pacific in summer time than in winter.
+pub fn zones_crossed(team_id: usize, other_team_id: usize, at: DateTime<Utc>) -> Option<i32> {
+    Some((utc_offset(team_id, at)? - utc_offset(other_team_id, at)?).abs())
+}
+
+/// The calendar date where the game is played.
+pub fn local_date(game: &Game) -> NaiveDate {
+    let zone = venue_zone(game.teams.home.team.id).unwrap_or(DEFAULT_ZONE);
+    game.game_date.with_timezone(&zone).date_naive()
+}
+
+/// Where one team's game falls in its schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRest {
+    pub game_pk: usize,
+    pub date: NaiveDate,
+    pub team_id: usize,
+    pub opponent_id: usize,
+    pub home: bool,
+    /// Full days off since the previous game, `None` for the first one.
+    pub days_rest: Option<i64>,
+    pub back_to_back: bool,
+    /// 1-based position in the current homestand or road trip.
+    pub stretch_game: usize,
+    pub stretch_length: usize,
+    /// Since the previous game, or since home for the first one.
+    pub zones_crossed: Option<i32>,
+    pub travel_km: Option<f64>,
+}
+
+impl GameRest {
+    /// "road trip 2/5" or "homestand 1/3"
+    pub fn stretch(&self) -> String {
+        format!(
+            "{} {}/{}",
+            if self.home { "homestand" } else { "road trip" },
+            self.stretch_game,
+            self.stretch_length
+        )
+    }
+}
+
+/// Walks a team's games in order and works out rest and travel for each.
+/// Postponed games are left out.
+pub fn team_schedule<'a>(
+    games: impl IntoIterator<Item = &'a Game>,
+    team_id: usize,
+) -> Vec<GameRest> {
+    let mut games: Vec<&Game> = games
+        .into_iter()
+        .filter(|game| game.has_competitor(team_id) && !game.is_postponed())
+        .collect();
+    games.sort_by_key(|game| game.game_date);
+
+    let mut rests: Vec<GameRest> = vec![];
+    let mut previous: Option<(NaiveDate, usize)> = None;
+    for game in games {
+        let date = local_date(game);
+        let venue = game.teams.home.team.id;
+        let (days_rest, from) = match previous {
+            Some((previous_date, previous_venue)) => {
+                (Some((date - previous_date).num_days() - 1), previous_venue)
+            }
+            None => (None, team_id),
+        };
+        rests.push(GameRest {
+            game_pk: game.game_pk,
+            date,
+            team_id,
+            opponent_id: game.opposition(team_id),
+            home: venue == team_id,
+            days_rest,
+            back_to_back: days_rest == Some(0),
+            stretch_game: 1,
+            stretch_length: 1,
+            zones_crossed: zones_crossed(from, venue, game.game_date),
+            travel_km: distance_km(from, venue),
+        });
+        previous = Some((date, venue));
+    }
+
+    let mut start = 0;
+    while start < rests.len() {
+        let home = rests[start].home;
+        let length = rests[start..]
+            .iter()
+            .take_while(|rest| rest.home == home)
+            .count();
+        for (index, rest) in rests[start..start + length].iter_mut().enumerate() {
+            rest.stretch_game = index + 1;
+            rest.stretch_length = length;
+        }
+        start += length;
+    }
+    rests
+}
+
+/// How both teams come into a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestComparison {
+    pub away: Option<GameRest>,
+    pub home: Option<GameRest>,
+}
+
+impl RestComparison {
+    /// Compares the teams in `game` given their earlier games in `games`.
+    pub fn new(game: &Game, games: &[Game]) -> Self {
+        let rest = |team_id| {
+            let schedule = games
+                .iter()
+                .filter(|earlier| earlier.game_pk != game.game_pk)
+                .chain(std::iter::once(game));
+            team_schedule(schedule, team_id)
+                .into_iter()
+                .find(|rest| rest.game_pk == game.game_pk)
+        };
+        Self {
+            away: rest(game.teams.away.team.id),
+            home: rest(game.teams.home.team.id),
+        }
+    }
+
+    /// The better rested team, when the other is on a back-to-back or has
+    /// had at least two fewer days off.
+    pub fn advantage(&self) -> Option<usize> {
+        let (away, home) = (self.away.as_ref()?, self.home.as_ref()?);
+        if away.back_to_back != home.back_to_back {
+            return Some(if away.back_to_back {
+                home.team_id
+            } else {
+                away.team_id
+            });
+        }
+        let (away_days, home_days) = (away.days_rest?, home.days_rest?);
+        if (away_days - home_days).abs() >= 2 {
+            Some(if away_days > home_days {
+                away.team_id
+            } else {
+                home.team_id
+            })
+        } else {
+            None
+        }
+    }
+
+    /// "Rest: EDM (SJS on a back-to-back)"
+    pub fn flag(&self, locale: Locale) -> Option<String> {
+        let team_id = self.advantage()?;
+        let (rested, tired) = match (&self.away, &self.home) {
+            (Some(away), Some(home)) if away.team_id == team_id => (away, home),
+            (Some(away), Some(home)) => (home, away),
+            _ => return None,
+        };
+        let abbreviation = |team_id| franchise::abbreviation(team_id).unwrap_or("?");
+        let flag = if tired.back_to_back {
+            locale.rest_back_to_back(abbreviation(rested.team_id), abbreviation(tired.team_id))
+        } else {
+            locale.rest_days_off(
+                abbreviation(rested.team_id),
+                rested.days_rest.unwrap_or_default(),
+                tired.days_rest.unwrap_or_default(),
+            )
+        };
+        Some(flag)
+    }
+}
+
+/// One line per game, for the command line.
+pub fn schedule_text(rests: &[GameRest]) -> String {
+    let abbreviation = |team_id| franchise::abbreviation(team_id).unwrap_or("?");
+    let mut text = String::new();
+    for rest in rests {
+        text.push_str(&format!(
+            "{} {} {:<3} {:>4} {:<15} {:>2} tz {:>5} km{}\n",
+            rest.date.format("%F"),
+            if rest.home { "vs" } else { " @" },
+            abbreviation(rest.opponent_id),
+            rest.days_rest
+                .map(|days| days.to_string())
+                .unwrap_or_default(),
+            rest.stretch(),
+            rest.zones_crossed.unwrap_or_default(),
+            rest.travel_km
+                .map(|km| km.round() as i64)
+                .unwrap_or_default(),
+            if rest.back_to_back { " B2B" } else { "" }
+        ));
+    }
+    let total: f64 = rests.iter().filter_map(|rest| rest.travel_km).sum();
+    let back_to_backs = rests.iter().filter(|rest| rest.back_to_back).count();
+    text.push_str(&format!(
+        "{} games, {} back-to-backs, {} km\n",
+        rests.len(),
+        back_to_backs,
+        total.round() as i64
+    ));
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+    use chrono::Duration;
+    use teams::{
+        ANAHEIM_DUCKS_ID, ARIZONA_COYOTES_ID, EDMONTON_OILERS_ID, LOS_ANGELES_KINGS_ID,
+        SAN_JOSE_SHARKS_ID,
+    };
+
+    fn game(template: &Game, game_pk: usize, days: i64, away: usize, home: usize) -> Game {
+        let mut game = template.clone();
+        game.game_pk = game_pk;
+        game.game_date = template.game_date + Duration::days(days);
+        game.teams.away.team.id = away;
+        game.teams.home.team.id = home;
+        game
+    }
+
+    #[test]
+    fn test_rest_and_travel() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let template = &schedule.dates[0].games[0];
+        let games = vec![
+            game(template, 1, 0, EDMONTON_OILERS_ID, SAN_JOSE_SHARKS_ID),
+            game(template, 2, 1, SAN_JOSE_SHARKS_ID, LOS_ANGELES_KINGS_ID),
+            game(template, 3, 3, SAN_JOSE_SHARKS_ID, ANAHEIM_DUCKS_ID),
+            game(template, 4, 5, EDMONTON_OILERS_ID, SAN_JOSE_SHARKS_ID),
+            // Edmonton plays in Los Angeles the night before.
+            game(template, 5, 4, EDMONTON_OILERS_ID, LOS_ANGELES_KINGS_ID),
+        ];
+
+        let rests = team_schedule(&games, SAN_JOSE_SHARKS_ID);
+        assert_eq!(rests.len(), 4);
+        assert_eq!(rests[0].days_rest, None);
+        assert_eq!(rests[0].travel_km, Some(0.0));
+        assert!(rests[1].back_to_back);
+        assert_eq!(rests[1].stretch(), "road trip 1/2");
+        assert_eq!(rests[2].days_rest, Some(1));
+        assert_eq!(rests[3].stretch(), "homestand 1/1");
+        let km = rests[1].travel_km.unwrap();
+        assert!((km - 492.0).abs() < 10.0, "{}", km);
+
+        let oilers = team_schedule(&games, EDMONTON_OILERS_ID);
+        assert_eq!(oilers[0].zones_crossed, Some(1));
+
+        let comparison = RestComparison::new(&games[3], &games);
+        assert_eq!(comparison.advantage(), Some(SAN_JOSE_SHARKS_ID));
+        assert_eq!(
+            comparison.flag(Locale::English).unwrap(),
+            "Rest: SJS (EDM on a back-to-back)"
+        );
+        assert_eq!(
+            comparison.flag(Locale::French).unwrap(),
+            "Repos : SJS (EDM joue deux soirs de suite)"
+        );
+        assert_eq!(RestComparison::new(&games[2], &games).advantage(), None);
+    }
+
+    #[test]
+    fn test_daylight_saving() {
+        let at = |text: &str| text.parse::<DateTime<Utc>>().unwrap();
+        // Arizona stays on standard time all year.
+        assert_eq!(
+            zones_crossed(
+                SAN_JOSE_SHARKS_ID,
+                ARIZONA_COYOTES_ID,
+                at("2023-01-15T03:00:00Z")
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            zones_crossed(
+                SAN_JOSE_SHARKS_ID,
+                ARIZONA_COYOTES_ID,
+                at("2023-03-20T02:00:00Z")
+            ),
+            Some(0)
+        );
+
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let mut late = schedule.dates[0].games[0].clone();
+        late.teams.home.team.id = SAN_JOSE_SHARKS_ID;
+        // 11:30 pm in San Jose on standard time, though daylight time
+        // would make it the next day.
+        late.game_date = at("2022-12-01T07:30:00Z");
+        assert_eq!(
+            local_date(&late),
+            NaiveDate::from_ymd_opt(2022, 11, 30).unwrap()
+        );
+    }
+}
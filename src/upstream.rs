@@ -0,0 +1,164 @@
+use crate::api;
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+
+/// Where raw API responses come from. Paths are relative to the API root,
+/// e.g. `api/v1/schedule?date=2022-11-20`.
+#[async_trait]
+pub trait Upstream: Send + Sync {
+    async fn get(&self, path: &str) -> Result<String, Error>;
+}
+
+pub async fn get_json<T: DeserializeOwned>(
+    upstream: &dyn Upstream,
+    path: &str,
+) -> Result<T, Error> {
+    let text = upstream.get(path).await?;
+    serde_json::from_str(&text).map_err(|err| anyhow!("{}: {}", path, err))
+}
+
+/// File name used to store the response for `path`: the path segments
+/// after `api/v1` followed by the query values, ignoring `expand`.
+///
+/// `api/v1/schedule?expand=schedule.linescore&date=2022-11-20` is stored
+/// as `schedule-2022-11-20.json`.
+pub fn fixture_name(path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut parts: Vec<&str> = path
+        .trim_start_matches("api/v1/")
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect();
+    parts.extend(
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .filter(|(key, _)| *key != "expand")
+            .map(|(_, value)| value),
+    );
+    let name: String = parts
+        .join("-")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.json", name)
+}
+
+/// Fetches from the live API, optionally saving every response as a
+/// fixture.
+pub struct HttpUpstream {
+    base_url: String,
+    record_to: Option<PathBuf>,
+}
+
+impl HttpUpstream {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            record_to: None,
+        }
+    }
+
+    pub fn recording(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.record_to = Some(dir.into());
+        self
+    }
+}
+
+impl Default for HttpUpstream {
+    fn default() -> Self {
+        Self::new(api::NHL_API)
+    }
+}
+
+#[async_trait]
+impl Upstream for HttpUpstream {
+    async fn get(&self, path: &str) -> Result<String, Error> {
+        let url = format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        let mut response = surf::get(&url).await.map_err(|err| err.into_inner())?;
+        // surf doesn't treat error statuses as failures, so check here to
+        // fall back to the cache and keep error pages out of the recordings.
+        if !response.status().is_success() {
+            return Err(anyhow!("{}: {}", url, response.status()));
+        }
+        let text = response
+            .body_string()
+            .await
+            .map_err(|err| err.into_inner())?;
+        if let Some(dir) = &self.record_to {
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(dir.join(fixture_name(path)), &text)?;
+        }
+        Ok(text)
+    }
+}
+
+/// Serves responses previously recorded by `HttpUpstream`.
+pub struct FixtureUpstream {
+    dir: PathBuf,
+}
+
+impl FixtureUpstream {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl Upstream for FixtureUpstream {
+    async fn get(&self, path: &str) -> Result<String, Error> {
+        let file = self.dir.join(fixture_name(path));
+        std::fs::read_to_string(&file).map_err(|err| anyhow!("{}: {}", file.display(), err))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NextGameSchedule;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_fixture_name() {
+        let date = NaiveDate::from_ymd_opt(2022, 11, 20).unwrap();
+        assert_eq!(
+            fixture_name(&api::schedule_path(date)),
+            "schedule-2022-11-20.json"
+        );
+        assert_eq!(
+            fixture_name("/api/v1/game/2022020275/content"),
+            "game-2022020275-content.json"
+        );
+        assert_eq!(
+            fixture_name(&api::schedule_range_path(date, date, Some(28))),
+            "schedule-2022-11-20-2022-11-20-28.json"
+        );
+    }
+
+    #[async_std::test]
+    async fn test_fixture_upstream() {
+        let upstream = FixtureUpstream::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data/fixtures"));
+        let date = NaiveDate::from_ymd_opt(2022, 11, 20).unwrap();
+        let schedule: NextGameSchedule = get_json(&upstream, &api::schedule_path(date))
+            .await
+            .unwrap();
+        assert_eq!(schedule.dates[0].games.len(), 7);
+        assert!(upstream
+            .get("api/v1/schedule?date=1900-01-01")
+            .await
+            .is_err());
+    }
+}
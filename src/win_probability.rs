@@ -0,0 +1,347 @@
+use crate::{franchise, ratings::Ratings, travel::RestComparison, Game};
+
+/// Pregame chances from the Elo ratings, adjusted for rest.
+#[derive(Debug, Clone)]
+pub struct WinModel {
+    pub ratings: Ratings,
+    /// Elo points taken off a team on the second night of a back-to-back
+    /// when the other team isn't.
+    pub back_to_back: f64,
+    /// Elo points per extra day off, up to `MAX_REST_DAYS` more than the
+    /// other team.
+    pub rest_day: f64,
+}
+
+pub const MAX_REST_DAYS: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Prediction {
+    pub game_pk: usize,
+    pub home_id: usize,
+    pub away_id: usize,
+    /// The chance the home team wins, overtime and shootouts included.
+    pub home: f64,
+}
+
+impl Prediction {
+    pub fn away(&self) -> f64 {
+        1.0 - self.home
+    }
+
+    /// The team more likely to win and its chance.
+    pub fn favorite(&self) -> (usize, f64) {
+        if self.home >= 0.5 {
+            (self.home_id, self.home)
+        } else {
+            (self.away_id, self.away())
+        }
+    }
+
+    /// "SJS 58%"
+    pub fn describe(&self) -> String {
+        let (team_id, chance) = self.favorite();
+        format!(
+            "{} {:.0}%",
+            franchise::abbreviation(team_id).unwrap_or("?"),
+            chance * 100.0
+        )
+    }
+}
+
+impl WinModel {
+    pub fn new(ratings: Ratings) -> Self {
+        Self {
+            ratings,
+            back_to_back: 30.0,
+            rest_day: 5.0,
+        }
+    }
+
+    /// Elo points the home team gains, or loses, from how rested the teams
+    /// are.
+    pub fn rest_adjustment(&self, rest: &RestComparison) -> f64 {
+        let (away, home) = match (&rest.away, &rest.home) {
+            (Some(away), Some(home)) => (away, home),
+            _ => return 0.0,
+        };
+        match (home.back_to_back, away.back_to_back) {
+            (true, false) => -self.back_to_back,
+            (false, true) => self.back_to_back,
+            _ => match (home.days_rest, away.days_rest) {
+                (Some(home_days), Some(away_days)) => {
+                    (home_days - away_days).clamp(-MAX_REST_DAYS, MAX_REST_DAYS) as f64
+                        * self.rest_day
+                }
+                _ => 0.0,
+            },
+        }
+    }
+
+    /// The chance for a game that hasn't started, given the rest of the
+    /// season's schedule in `season_games`.
+    pub fn predict(&self, game: &Game, season_games: &[Game]) -> Option<Prediction> {
+        if !game.is_preview() {
+            return None;
+        }
+        Some(self.prediction(game, season_games))
+    }
+
+    fn prediction(&self, game: &Game, season_games: &[Game]) -> Prediction {
+        let (home_id, away_id) = (game.teams.home.team.id, game.teams.away.team.id);
+        let rest = RestComparison::new(game, season_games);
+        let settings = &self.ratings.settings;
+        let home = settings.expected(
+            self.ratings.rating(home_id) + settings.home_ice + self.rest_adjustment(&rest),
+            self.ratings.rating(away_id),
+        );
+        Prediction {
+            game_pk: game.game_pk,
+            home_id,
+            away_id,
+            home,
+        }
+    }
+}
+
+/// Predicts every finished game from only the results before it, then
+/// adds its result, as the model would have seen the season. Returns each
+/// home team's predicted chance and whether it won.
+pub fn backtest(games: &[Game]) -> Vec<(f64, bool)> {
+    let mut finished: Vec<&Game> = games
+        .iter()
+        .filter(|game| game.is_finished() && (game.is_regular_season() || game.is_playoffs()))
+        .collect();
+    finished.sort_by_key(|game| (game.game_date, game.game_pk));
+    let mut model = WinModel::new(Ratings::default());
+    let mut results = vec![];
+    for game in finished {
+        let prediction = model.prediction(game, games);
+        results.push((prediction.home, game.winner() == prediction.home_id));
+        model.ratings.replay([game]);
+    }
+    results
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationBucket {
+    /// The average predicted chance in the bucket.
+    pub predicted: f64,
+    /// How often the team actually won.
+    pub observed: f64,
+    pub games: usize,
+}
+
+/// Groups predictions into `buckets` equal ranges of predicted chance.
+/// Empty buckets are left out.
+pub fn calibration(results: &[(f64, bool)], buckets: usize) -> Vec<CalibrationBucket> {
+    let mut sums = vec![(0.0, 0, 0); buckets];
+    for (predicted, won) in results {
+        let index = ((predicted * buckets as f64) as usize).min(buckets - 1);
+        let (total, wins, games) = &mut sums[index];
+        *total += predicted;
+        *wins += usize::from(*won);
+        *games += 1;
+    }
+    sums.into_iter()
+        .filter(|(_, _, games)| *games > 0)
+        .map(|(total, wins, games)| CalibrationBucket {
+            predicted: total / games as f64,
+            observed: wins as f64 / games as f64,
+            games,
+        })
+        .collect()
+}
+
+/// Mean squared error of the predictions; always guessing 50% scores 0.25.
+pub fn brier_score(results: &[(f64, bool)]) -> f64 {
+    let total: f64 = results
+        .iter()
+        .map(|(predicted, won)| (predicted - if *won { 1.0 } else { 0.0 }).powi(2))
+        .sum();
+    total / results.len().max(1) as f64
+}
+
+/// One line per bucket, for the command line.
+pub fn calibration_text(results: &[(f64, bool)]) -> String {
+    let mut text = String::new();
+    for bucket in calibration(results, 10) {
+        text.push_str(&format!(
+            "predicted {:>5.1}%  won {:>5.1}%  {:>4} games\n",
+            bucket.predicted * 100.0,
+            bucket.observed * 100.0,
+            bucket.games
+        ));
+    }
+    text.push_str(&format!(
+        "{} games, Brier score {:.4}\n",
+        results.len(),
+        brier_score(results)
+    ));
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{teams, NextGameSchedule};
+    use chrono::{Duration, NaiveDate};
+    use serde_json::json;
+
+    /// A small xorshift generator so the season is the same every run.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn poisson(&mut self, mean: f64) -> usize {
+            let limit = (-mean).exp();
+            let mut product = self.next();
+            let mut count = 0;
+            while product > limit {
+                product *= self.next();
+                count += 1;
+            }
+            count
+        }
+    }
+
+    /// A made-up season for checking the backtest itself: every team
+    /// plays every other home and away, twice, with goals drawn from
+    /// hidden team strengths rather than from the Elo model itself.
+    fn simulated_season() -> Vec<Game> {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let template = serde_json::to_value(&schedule.dates[0].games[0]).unwrap();
+        let mut team_ids: Vec<usize> = teams::TEAM_REGISTRY.keys().copied().collect();
+        team_ids.sort();
+        team_ids.truncate(team_ids.len() / 2 * 2);
+        let count = team_ids.len();
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let strengths: Vec<f64> = team_ids.iter().map(|_| rng.next() * 0.6 - 0.3).collect();
+
+        // Round robins by the circle method, so every team plays once a
+        // round, with home and away swapped every other time through.
+        let start = NaiveDate::from_ymd_opt(2022, 10, 11).unwrap();
+        let mut games = vec![];
+        for round in 0..(count - 1) * 4 {
+            let mut circle: Vec<usize> = (1..count).collect();
+            circle.rotate_left(round % (count - 1));
+            circle.insert(0, 0);
+            for index in 0..count / 2 {
+                let (mut home, mut away) = (circle[index], circle[count - 1 - index]);
+                if (round / (count - 1) + index) % 2 == 1 {
+                    std::mem::swap(&mut home, &mut away);
+                }
+                // Rounds are two days apart, give or take one, so some
+                // teams end up on back-to-backs and some well rested.
+                let day = start + Duration::days(round as i64 * 2 + (rng.next() * 2.0) as i64);
+                let strength = strengths[home] - strengths[away];
+                let mut home_goals = rng.poisson(3.1 * (strength / 2.0).exp());
+                let mut away_goals = rng.poisson(2.9 * (-strength / 2.0).exp());
+                let overtime = home_goals == away_goals;
+                if overtime {
+                    if rng.next() < 0.5 + strength / 4.0 {
+                        home_goals += 1;
+                    } else {
+                        away_goals += 1;
+                    }
+                }
+                let mut game = template.clone();
+                game["gamePk"] = json!(2022020001 + games.len());
+                game["gameDate"] = json!(format!("{}T23:00:00Z", day));
+                game["season"] = json!("20222023");
+                game["gameType"] = json!("R");
+                game["status"]["abstractGameState"] = json!("Final");
+                game["status"]["detailedState"] = json!("Final");
+                game["teams"]["home"]["team"]["id"] = json!(team_ids[home]);
+                game["teams"]["away"]["team"]["id"] = json!(team_ids[away]);
+                game["teams"]["home"]["score"] = json!(home_goals);
+                game["teams"]["away"]["score"] = json!(away_goals);
+                game["linescore"]["currentPeriod"] = json!(if overtime { 4 } else { 3 });
+                games.push(serde_json::from_value(game).unwrap());
+            }
+        }
+        games
+    }
+
+    /// The 2022-23 regular season and playoffs as the API returned them.
+    #[cfg(feature = "native_http")]
+    fn recorded_season() -> Vec<Game> {
+        let season = crate::season::Season { start_year: 2022 };
+        let name = crate::upstream::fixture_name(&crate::api::season_schedule_path(season, None));
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("data/fixtures")
+            .join(&name);
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+            panic!(
+                "{}: {}; record it with `games calibration --season 2022 --record data/fixtures`",
+                path.display(),
+                err
+            )
+        });
+        let schedule: NextGameSchedule = serde_json::from_str(&text).unwrap();
+        schedule
+            .dates
+            .into_iter()
+            .flat_map(|date| date.games)
+            .collect()
+    }
+
+    #[test]
+    fn test_backtest() {
+        // Checks the replay, not the model: the simulated goals come from
+        // hidden strengths, so ratings that learn them beat a coin flip.
+        let games = simulated_season();
+        let results = backtest(&games);
+        assert_eq!(results.len(), games.len());
+        let settled = &results[results.len() / 8..];
+        assert!(
+            brier_score(settled) < 0.245,
+            "{}",
+            calibration_text(settled)
+        );
+    }
+
+    #[cfg(feature = "native_http")]
+    #[test]
+    #[ignore = "needs a recorded season, see recorded_season"]
+    fn test_calibration() {
+        let games = recorded_season();
+        let results = backtest(&games);
+        // Leave out the first month, while every team is still near 1500.
+        let settled = &results[results.len() / 8..];
+        let brier = brier_score(settled);
+        assert!(brier < 0.25, "{}", calibration_text(settled));
+        for bucket in calibration(settled, 5) {
+            if bucket.games >= 100 {
+                assert!(
+                    (bucket.predicted - bucket.observed).abs() < 0.06,
+                    "{}",
+                    calibration_text(settled)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_predict() {
+        let schedule: NextGameSchedule =
+            serde_json::from_str(include_str!("../data/fixtures/schedule-2022-11-20.json"))
+                .unwrap();
+        let games = &schedule.dates[0].games;
+        let model = WinModel::new(Ratings::from_games(games));
+        let upcoming = games.iter().find(|game| game.is_preview()).unwrap();
+        let prediction = model.predict(upcoming, games).unwrap();
+        // Two unrated teams, so only home ice counts.
+        assert!((prediction.home - 0.5715).abs() < 0.001);
+        assert_eq!(prediction.describe(), "VGK 57%");
+        let finished = games.iter().find(|game| game.is_finished()).unwrap();
+        assert!(model.predict(finished, games).is_none());
+    }
+}